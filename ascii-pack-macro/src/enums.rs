@@ -0,0 +1,675 @@
+use darling::util::Flag;
+use darling::FromAttributes;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{parse_quote, DataEnum, DeriveInput, Expr, Fields, Generics, Lit, Variant};
+
+use crate::{
+    add_field_bounds, layout, process_field, size, uses_type_params, FieldAttr, FieldTarget,
+    OnError, PackField, Trailing,
+};
+
+#[derive(Debug, Default, FromAttributes)]
+#[darling(attributes(pack_enum))]
+struct PackEnumArgs {
+    /// Width of the leading tag shared by every variant. When this is
+    /// omitted, variant tags are matched as prefixes of the input.
+    tag_size: Option<usize>,
+    /// Match the codes of a code enum without regard to ascii case.
+    ignore_case: Flag,
+    /// Select the variant by trying to unpack each one in declaration
+    /// order instead of by a leading tag.
+    untagged: Flag,
+    /// What `from_ascii` accepts after the variant.
+    trailing: Option<Trailing>,
+}
+
+#[derive(Debug, Default, FromAttributes)]
+#[darling(attributes(pack))]
+struct PackCodeArgs {
+    /// The code this unit variant is packed as.
+    code: Option<String>,
+    /// Marks the catch-all variant storing any unknown code.
+    other: Flag,
+}
+
+/// A variant of a tagged enum along with the tag selecting it.
+struct TaggedVariant<'a> {
+    variant: &'a Variant,
+    tag: String,
+    fields: Vec<PackField<'a>>,
+}
+
+impl<'a> TaggedVariant<'a> {
+    /// A variant selected by `tag`, with the attributes of its fields parsed.
+    fn new(variant: &'a Variant, tag: String) -> syn::Result<TaggedVariant<'a>> {
+        Ok(TaggedVariant {
+            variant,
+            tag,
+            fields: PackField::parse_all(&variant.fields)?,
+        })
+    }
+
+    /// A variant of an untagged enum, which is written without any tag.
+    fn untagged(variant: &'a Variant) -> syn::Result<TaggedVariant<'a>> {
+        TaggedVariant::new(variant, String::new())
+    }
+}
+
+/// Reads the `#[pack_tag = "..."]` attribute of a variant.
+fn parse_tag(variant: &Variant) -> syn::Result<Option<String>> {
+    let mut tag = None;
+    for attr in variant.attrs.iter() {
+        if !attr.path().is_ident("pack_tag") {
+            continue;
+        }
+
+        if tag.is_some() {
+            return Err(syn::Error::new(
+                attr.span(),
+                "Only one pack_tag attribute is allowed on each variant!",
+            ));
+        }
+
+        let name_value = attr.meta.require_name_value()?;
+        match &name_value.value {
+            Expr::Lit(expr) => match &expr.lit {
+                Lit::Str(lit) => tag = Some(lit.value()),
+                _ => return Err(syn::Error::new(expr.span(), "pack_tag must be a string!")),
+            },
+            other => return Err(syn::Error::new(other.span(), "pack_tag must be a string!")),
+        }
+    }
+    Ok(tag)
+}
+
+/// Generates the `from_ascii` and `to_ascii` tokens for a single variant.
+/// The `from_ascii` tokens start parsing the variant's fields after the tag
+/// and evaluate to the constructed variant, while the `to_ascii` tokens form
+/// a match arm which writes the tag followed by the fields.
+fn generate_variant_tokens(tagged: &TaggedVariant) -> syn::Result<(TokenStream2, TokenStream2)> {
+    let variant = tagged.variant;
+    let ident = &variant.ident;
+    let tag = &tagged.tag;
+    let tag_len = tag.len();
+
+    let bindings: Vec<syn::Ident> = variant
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(name) => format_ident!("__{}", name),
+            None => format_ident!("__{}", index),
+        })
+        .collect();
+
+    let mut from_ascii_tokens = quote! {
+        let mut left_bound = #tag_len;
+    };
+    let mut to_ascii_tokens = match tag.is_empty() {
+        true => TokenStream2::new(),
+        false => quote! { result.push_str(#tag); },
+    };
+
+    let mut targets: Vec<FieldTarget> = variant
+        .fields
+        .iter()
+        .zip(bindings.iter())
+        .enumerate()
+        .map(|(index, (field, binding))| {
+            FieldTarget::binding(binding, field_name(variant, index, field))
+        })
+        .collect();
+    FieldTarget::resolve_lengths(&tagged.fields, &mut targets)?;
+    FieldTarget::resolve_delimiters(&tagged.fields, &mut targets, None, None, None)?;
+
+    for ((field, binding), target) in tagged.fields.iter().zip(bindings.iter()).zip(targets) {
+        let ty = &field.field.ty;
+        if let FieldAttr::Pack(args) = &field.attr {
+            if let Some(when) = &args.when {
+                return Err(syn::Error::new(
+                    when.span(),
+                    "when can only be used on the fields of structs!",
+                ));
+            }
+        }
+
+        from_ascii_tokens = quote! {
+            #from_ascii_tokens
+            let mut #binding: #ty = ::core::default::Default::default();
+        };
+        let (from, to) = process_field(
+            from_ascii_tokens,
+            to_ascii_tokens,
+            field,
+            &target,
+            OnError::Return,
+        )?;
+        from_ascii_tokens = from;
+        to_ascii_tokens = to;
+    }
+
+    // the same tokens construct the variant and destructure it in a match arm
+    let construct = match &variant.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote! { Self::#ident { #(#names: #bindings),* } }
+        }
+        Fields::Unnamed(_) => quote! { Self::#ident(#(#bindings),*) },
+        Fields::Unit => quote! { Self::#ident },
+    };
+
+    let from_ascii_tokens = quote! {
+        #from_ascii_tokens
+        return Ok((#construct, left_bound));
+    };
+    let to_ascii_tokens = quote! {
+        #construct => {
+            #to_ascii_tokens
+        }
+    };
+
+    Ok((from_ascii_tokens, to_ascii_tokens))
+}
+
+/// Generates the `AsciiPack` and `AsciiPackUnsized` impls for an enum.
+/// Enums with `#[pack(...)]` variants are derived as code enums, and
+/// all other enums are derived as tagged enums.
+pub(crate) fn derive_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<TokenStream2> {
+    let args = PackEnumArgs::from_attributes(&input.attrs)?;
+    let is_code_enum = data.variants.iter().any(|variant| {
+        variant
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("pack"))
+    });
+
+    match (is_code_enum, args.untagged.is_present()) {
+        (true, true) => Err(syn::Error::new(
+            input.ident.span(),
+            "Code enums cannot be untagged!",
+        )),
+        (true, false) => derive_code_enum(input, data, args),
+        (false, true) => derive_untagged_enum(input, data, args),
+        (false, false) => derive_tagged_enum(input, data, args),
+    }
+}
+
+/// Returns the generics of an enum with the bounds required to unpack
+/// and pack the fields of its variants.
+fn variant_generics(input: &DeriveInput, variants: &[TaggedVariant]) -> syn::Result<Generics> {
+    let mut generics = input.generics.clone();
+    let fields = variants.iter().flat_map(|tagged| tagged.fields.iter());
+    add_field_bounds(&mut generics, fields.clone())?;
+
+    // variant fields are default initialized before being unpacked
+    for field in fields {
+        let ty = &field.field.ty;
+        if uses_type_params(ty, &generics) {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote! { #ty: ::core::default::Default });
+        }
+    }
+    Ok(generics)
+}
+
+/// Returns the name of a variant field as used in error messages.
+fn field_name(variant: &Variant, index: usize, field: &syn::Field) -> String {
+    match &field.ident {
+        Some(name) => format!("{}::{}", variant.ident, name),
+        None => format!("{}::{}", variant.ident, index),
+    }
+}
+
+/// Generates the `SIZE` of an enum, which is known when every variant packs
+/// to the same width, along with the compile-time checks of the declared
+/// sizes of its variant fields. See [`size::size_check_impl`] for the
+/// meaning of the returned check tokens.
+fn enum_size(
+    input: &DeriveInput,
+    generics: &Generics,
+    variants: &[TaggedVariant],
+) -> syn::Result<(TokenStream2, TokenStream2, TokenStream2)> {
+    let mut sizes = Vec::new();
+    let mut checks = TokenStream2::new();
+    for tagged in variants.iter() {
+        let variant = tagged.variant;
+        sizes.push(size::fields_size(tagged.tag.len(), &tagged.fields)?);
+
+        let names = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| field_name(variant, index, field));
+        let variant_checks = size::size_checks(&tagged.fields, names)?;
+        checks = quote! {
+            #checks
+            #variant_checks
+        };
+    }
+
+    let (check_impl, check) = size::size_check_impl(&input.ident, generics, checks);
+    let enum_size = quote! { ::ascii_pack::common_size(&[#(#sizes),*]) };
+    Ok((enum_size, check_impl, check))
+}
+
+/// Generates the `LAYOUT` of an enum, which describes each variant along
+/// with the layout of its fields following the tag.
+fn enum_layout(input: &DeriveInput, variants: &[TaggedVariant]) -> syn::Result<TokenStream2> {
+    let mut descriptors = Vec::new();
+    for tagged in variants.iter() {
+        let prefix = tagged.tag.len();
+        let size = size::fields_size(prefix, &tagged.fields)?;
+        let nested = layout::fields_layout(prefix, &tagged.fields, false)?;
+        let text = Some(tagged.tag.as_str()).filter(|tag| !tag.is_empty());
+        descriptors.push(layout::variant_layout(
+            &input.ident,
+            &tagged.variant.ident,
+            size,
+            text,
+            nested,
+        ));
+    }
+
+    Ok(quote! { &[#(#descriptors),*] })
+}
+
+/// Generates the impls for an enum without tags, where each variant is
+/// attempted in declaration order and the first one to unpack is chosen.
+/// `from_ascii` additionally requires the input remaining after the variant
+/// to pass the `trailing` check, so that a shorter layout cannot match a
+/// longer record.
+fn derive_untagged_enum(
+    input: &DeriveInput,
+    data: &DataEnum,
+    args: PackEnumArgs,
+) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let lit_ident = ident.to_string();
+    let untagged = data
+        .variants
+        .iter()
+        .map(TaggedVariant::untagged)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let generics = variant_generics(input, &untagged)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    if args.tag_size.is_some() {
+        return Err(syn::Error::new(
+            ident.span(),
+            "tag_size cannot be used with untagged enums!",
+        ));
+    }
+
+    let trailing = args.trailing.unwrap_or_default();
+    let (enum_size, check_impl, check) = enum_size(input, &generics, &untagged)?;
+    let enum_layout = enum_layout(input, &untagged)?;
+    let layout_impl = layout::layout_impl(ident, &generics);
+
+    let mut from_ascii_tokens = TokenStream2::new();
+    let mut from_unsized_tokens = TokenStream2::new();
+    let mut to_arms = TokenStream2::new();
+    for tagged in untagged.iter() {
+        let variant = tagged.variant;
+        if parse_tag(variant)?.is_some() {
+            return Err(syn::Error::new(
+                variant.span(),
+                "Variants of untagged enums cannot have a pack_tag!",
+            ));
+        }
+
+        let lit_variant = variant.ident.to_string();
+        let (from, to) = generate_variant_tokens(tagged)?;
+        let attempt = quote! {
+            (|| -> ::core::result::Result<(Self, usize), ::ascii_pack::AsciiPackError> {
+                #from
+            })()
+        };
+
+        from_ascii_tokens = quote! {
+            #from_ascii_tokens
+            match #attempt {
+                Ok((result, consumed)) => match #trailing.check(input, consumed) {
+                    Ok(()) => return Ok(result),
+                    Err(e) => errors.push((#lit_variant, e)),
+                },
+                Err(e) => errors.push((#lit_variant, e)),
+            }
+        };
+        from_unsized_tokens = quote! {
+            #from_unsized_tokens
+            match #attempt {
+                Ok(result) => return Ok(result),
+                Err(e) => errors.push((#lit_variant, e)),
+            }
+        };
+        to_arms = quote! {
+            #to_arms
+            #to
+        };
+    }
+
+    Ok(quote! {
+        #check_impl
+        #layout_impl
+
+        impl #impl_generics ::ascii_pack::AsciiPack for #ident #ty_generics #where_clause {
+            const SIZE: Option<usize> = #enum_size;
+            const LAYOUT: &'static [::ascii_pack::FieldLayout] = #enum_layout;
+
+            fn from_ascii(input: &str) -> ::core::result::Result<Self, ::ascii_pack::AsciiPackError> {
+                #check
+                let mut errors = Vec::new();
+                #from_ascii_tokens
+                Err(::ascii_pack::AsciiPackError::NoMatchingVariant { name: #lit_ident, errors })
+            }
+
+            fn to_ascii(&self) -> ::core::result::Result<String, ::ascii_pack::AsciiPackError> {
+                #check
+                let mut result = String::new();
+                match self {
+                    #to_arms
+                }
+                Ok(result)
+            }
+        }
+
+        impl #impl_generics ::ascii_pack::AsciiPackUnsized for #ident #ty_generics #where_clause {
+            fn from_ascii_unsized(input: &str) -> ::core::result::Result<(Self, usize), ::ascii_pack::AsciiPackError> {
+                #check
+                let mut errors = Vec::new();
+                #from_unsized_tokens
+                Err(::ascii_pack::AsciiPackError::NoMatchingVariant { name: #lit_ident, errors })
+            }
+        }
+    })
+}
+
+/// Generates the impls for a unit-only enum where every variant is packed
+/// as its `#[pack(code = "...")]`. The code is matched independently of any
+/// space padding around it, and an optional `#[pack(other)]` variant of the
+/// form `Other(String)` stores any code that is not otherwise matched.
+fn derive_code_enum(
+    input: &DeriveInput,
+    data: &DataEnum,
+    args: PackEnumArgs,
+) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let lit_ident = ident.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    if args.trailing.is_some() {
+        return Err(syn::Error::new(
+            ident.span(),
+            "trailing cannot be used with code enums, which match the entire input!",
+        ));
+    }
+
+    let mut codes: Vec<(&syn::Ident, String)> = Vec::new();
+    let mut other = None;
+    let mut descriptors = Vec::new();
+    for variant in data.variants.iter() {
+        let variant_args = PackCodeArgs::from_attributes(&variant.attrs)?;
+        match (variant_args.code, variant_args.other.is_present()) {
+            (Some(code), false) => {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new(
+                        variant.span(),
+                        "Variants with a pack code must be unit variants!",
+                    ));
+                }
+                let duplicate = codes.iter().find(|(_, existing)| match args.ignore_case.is_present() {
+                    true => existing.eq_ignore_ascii_case(&code),
+                    false => *existing == code,
+                });
+                if let Some((existing, _)) = duplicate {
+                    return Err(syn::Error::new(
+                        variant.span(),
+                        format!("pack code \"{}\" is already used by variant {}!", code, existing),
+                    ));
+                }
+                let size = code.len();
+                descriptors.push(layout::variant_layout(
+                    ident,
+                    &variant.ident,
+                    quote! { Some(#size) },
+                    Some(&code),
+                    quote! { &[] },
+                ));
+                codes.push((&variant.ident, code));
+            }
+            (None, true) => {
+                if other.is_some() {
+                    return Err(syn::Error::new(
+                        variant.span(),
+                        "Only one variant may be marked as #[pack(other)]!",
+                    ));
+                }
+                if variant.fields.len() != 1 || !matches!(variant.fields, Fields::Unnamed(_)) {
+                    return Err(syn::Error::new(
+                        variant.span(),
+                        "The #[pack(other)] variant must have a single unnamed field, e.g. `Other(String)`!",
+                    ));
+                }
+                descriptors.push(layout::variant_layout(
+                    ident,
+                    &variant.ident,
+                    quote! { None },
+                    None,
+                    quote! { &[] },
+                ));
+                other = Some(&variant.ident);
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    "Every variant of a code enum requires either #[pack(code = \"...\")] or #[pack(other)]!",
+                ))
+            }
+        }
+    }
+
+    let matches_code = |value: TokenStream2, code: &String| match args.ignore_case.is_present() {
+        true => quote! { #value.eq_ignore_ascii_case(#code) },
+        false => quote! { #value == #code },
+    };
+
+    let mut from_ascii_tokens = quote! {
+        let code = input.trim_matches(' ');
+    };
+    let mut to_arms = TokenStream2::new();
+    for (variant, code) in codes.iter() {
+        let condition = matches_code(quote! { code }, code);
+        from_ascii_tokens = quote! {
+            #from_ascii_tokens
+            if #condition {
+                return Ok(Self::#variant);
+            }
+        };
+        to_arms = quote! {
+            #to_arms
+            Self::#variant => Ok(#code.to_owned()),
+        };
+    }
+    from_ascii_tokens = match other {
+        Some(variant) => {
+            to_arms = quote! {
+                #to_arms
+                Self::#variant(code) => Ok(code.to_string()),
+            };
+            quote! {
+                #from_ascii_tokens
+                Ok(Self::#variant(code.into()))
+            }
+        }
+        None => quote! {
+            #from_ascii_tokens
+            Err(::ascii_pack::AsciiPackError::Unpack(
+                format!("'{}' is not a known code of {}", code, #lit_ident)))
+        },
+    };
+
+    // unsized unpacking can only match the known codes, trying longer
+    // codes first so that a code is never shadowed by its own prefix.
+    codes.sort_by_key(|(_, code)| std::cmp::Reverse(code.len()));
+    let mut from_unsized_tokens = TokenStream2::new();
+    for (variant, code) in codes.iter() {
+        let code_len = code.len();
+        let condition = matches_code(quote! { prefix }, code);
+        from_unsized_tokens = quote! {
+            #from_unsized_tokens
+            if let Some(prefix) = input.get(..#code_len) {
+                if #condition {
+                    return Ok((Self::#variant, #code_len));
+                }
+            }
+        };
+    }
+
+    let layout_impl = layout::layout_impl(ident, &input.generics);
+
+    Ok(quote! {
+        #layout_impl
+
+        impl #impl_generics ::ascii_pack::AsciiPack for #ident #ty_generics #where_clause {
+            const LAYOUT: &'static [::ascii_pack::FieldLayout] = &[#(#descriptors),*];
+            const PAD: char = ' ';
+
+            fn from_ascii(input: &str) -> ::core::result::Result<Self, ::ascii_pack::AsciiPackError> {
+                #from_ascii_tokens
+            }
+
+            fn to_ascii(&self) -> ::core::result::Result<String, ::ascii_pack::AsciiPackError> {
+                match self {
+                    #to_arms
+                }
+            }
+        }
+
+        impl #impl_generics ::ascii_pack::AsciiPackUnsized for #ident #ty_generics #where_clause {
+            fn from_ascii_unsized(input: &str) -> ::core::result::Result<(Self, usize), ::ascii_pack::AsciiPackError> {
+                #from_unsized_tokens
+                Err(::ascii_pack::AsciiPackError::Unpack(
+                    format!("No code of {} matches the start of '{}'", #lit_ident, input)))
+            }
+        }
+    })
+}
+
+/// Generates the `AsciiPack` and `AsciiPackUnsized` impls for an enum
+/// whose variants are selected by a leading `#[pack_tag = "..."]`.
+fn derive_tagged_enum(
+    input: &DeriveInput,
+    data: &DataEnum,
+    args: PackEnumArgs,
+) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let mut variants = Vec::new();
+    for variant in data.variants.iter() {
+        let tag = match parse_tag(variant)? {
+            Some(tag) => tag,
+            None => return Err(syn::Error::new(
+                variant.span(),
+                "Every variant of an AsciiPack enum requires a #[pack_tag = \"...\"] attribute!",
+            )),
+        };
+
+        if let Some(tag_size) = args.tag_size {
+            if tag.len() != tag_size {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    format!(
+                        "pack_tag \"{}\" does not match the tag_size of {}!",
+                        tag, tag_size
+                    ),
+                ));
+            }
+        }
+
+        if let Some(other) = variants.iter().find(|v: &&TaggedVariant| v.tag == tag) {
+            return Err(syn::Error::new(
+                variant.span(),
+                format!(
+                    "pack_tag \"{}\" is already used by variant {}!",
+                    tag, other.variant.ident
+                ),
+            ));
+        }
+
+        variants.push(TaggedVariant::new(variant, tag)?);
+    }
+
+    let generics = variant_generics(input, &variants)?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let enum_layout = enum_layout(input, &variants)?;
+    let layout_impl = layout::layout_impl(ident, &generics);
+
+    // without a fixed tag size, longer tags are tried first so that a tag
+    // is never shadowed by another tag which happens to be its prefix.
+    if args.tag_size.is_none() {
+        variants.sort_by_key(|v| std::cmp::Reverse(v.tag.len()));
+    }
+
+    let mut from_arms = TokenStream2::new();
+    let mut to_arms = TokenStream2::new();
+    for tagged in variants.iter() {
+        let tag = &tagged.tag;
+        let (from, to) = generate_variant_tokens(tagged)?;
+        let condition = match args.tag_size {
+            Some(tag_size) => quote! { input.get(..#tag_size) == Some(#tag) },
+            None => quote! { input.starts_with(#tag) },
+        };
+        from_arms = quote! {
+            #from_arms
+            if #condition {
+                #from
+            }
+        };
+        to_arms = quote! {
+            #to_arms
+            #to
+        };
+    }
+
+    let lit_ident = ident.to_string();
+    let trailing = args.trailing.unwrap_or_default();
+    let (enum_size, check_impl, check) = enum_size(input, &generics, &variants)?;
+
+    Ok(quote! {
+        #check_impl
+        #layout_impl
+
+        impl #impl_generics ::ascii_pack::AsciiPack for #ident #ty_generics #where_clause {
+            const SIZE: Option<usize> = #enum_size;
+            const LAYOUT: &'static [::ascii_pack::FieldLayout] = #enum_layout;
+
+            fn from_ascii(input: &str) -> ::core::result::Result<Self, ::ascii_pack::AsciiPackError> {
+                let (result, consumed) = <Self as ::ascii_pack::AsciiPackUnsized>::from_ascii_unsized(input)?;
+                #trailing.check(input, consumed)?;
+                Ok(result)
+            }
+
+            fn to_ascii(&self) -> ::core::result::Result<String, ::ascii_pack::AsciiPackError> {
+                #check
+                let mut result = String::new();
+                match self {
+                    #to_arms
+                }
+                Ok(result)
+            }
+        }
+
+        impl #impl_generics ::ascii_pack::AsciiPackUnsized for #ident #ty_generics #where_clause {
+            fn from_ascii_unsized(input: &str) -> ::core::result::Result<(Self, usize), ::ascii_pack::AsciiPackError> {
+                #check
+                #from_arms
+                Err(::ascii_pack::AsciiPackError::Unpack(
+                    format!("No variant of {} matches the tag of '{}'", #lit_ident, input)))
+            }
+        }
+    })
+}
@@ -0,0 +1,194 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::Type;
+
+use crate::{size, FieldAttr, PackField, Padding};
+
+/// Formats a type as it would be written in source, without the
+/// spacing `to_string` places around punctuation between tokens.
+pub(crate) fn type_name(ty: &Type) -> String {
+    let mut name = ty.to_token_stream().to_string();
+    for (spaced, compact) in [
+        (" < ", "<"),
+        (" <", "<"),
+        (" >", ">"),
+        (" :: ", "::"),
+        (" ,", ","),
+        ("& ", "&"),
+    ] {
+        name = name.replace(spaced, compact);
+    }
+    name
+}
+
+/// Tokens of the `Option<Align>` and `Option<char>` describing the padding
+/// of a field, which is only applied when the field has a declared size.
+fn padding_tokens(padding: Option<Padding>) -> (TokenStream2, TokenStream2) {
+    match padding {
+        Some(Padding { align, pad, .. }) => (quote! { Some(#align) }, quote! { Some(#pad) }),
+        None => (quote! { None }, quote! { None }),
+    }
+}
+
+/// Tokens of a single `FieldLayout` descriptor starting at `offset`.
+fn field_layout(
+    pack_field: &PackField,
+    name: &str,
+    offset: TokenStream2,
+) -> syn::Result<TokenStream2> {
+    let PackField { field, attr } = pack_field;
+    let ty = &field.ty;
+    let type_name = type_name(ty);
+    let (kind, size, padding, text, nested) = match attr {
+        FieldAttr::Ignore => (
+            quote! { PackIgnore },
+            size::field_size(pack_field)?,
+            None,
+            None,
+            quote! { &[] },
+        ),
+        FieldAttr::Pack(args) => {
+            let packed = args.packed_type(field);
+            let padding = match args.size {
+                Some(_) => Some(Padding::new(
+                    args.pad_left,
+                    args.align,
+                    args.pad,
+                    &args.trim,
+                    field,
+                    (!args.is_formatted()).then_some(packed),
+                )?),
+                None => None,
+            };
+            let nested = match args.is_formatted() {
+                true => quote! { &[] },
+                false => quote! { <#packed as ::ascii_pack::AsciiPack>::LAYOUT },
+            };
+            (
+                quote! { Pack },
+                size::present_size(pack_field)?,
+                padding,
+                None,
+                nested,
+            )
+        }
+        FieldAttr::PackVec(args) => {
+            let item = args.item_type(ty)?;
+            let (size, padding) = match &args.size {
+                Some(size) => (
+                    quote! { Some(#size) },
+                    Some(Padding::new(
+                        args.pad_left,
+                        args.align,
+                        args.pad,
+                        &args.trim,
+                        field,
+                        args.with.is_none().then_some(&item),
+                    )?),
+                ),
+                None => (quote! { None }, None),
+            };
+            let nested = match &args.with {
+                Some(_) => quote! { &[] },
+                None => quote! { <#item as ::ascii_pack::AsciiPack>::LAYOUT },
+            };
+            (quote! { PackVec }, size, padding, None, nested)
+        }
+        FieldAttr::PackStatic(args) => (
+            quote! { PackStatic },
+            size::field_size(pack_field)?,
+            None,
+            Some(args.text.clone()),
+            quote! { &[] },
+        ),
+    };
+
+    let (align, pad) = padding_tokens(padding);
+    let text = match text {
+        Some(text) => quote! { Some(#text) },
+        None => quote! { None },
+    };
+
+    Ok(quote! {
+        ::ascii_pack::FieldLayout {
+            name: #name,
+            kind: ::ascii_pack::FieldKind::#kind,
+            size: #size,
+            offset: #offset,
+            align: #align,
+            pad: #pad,
+            text: #text,
+            type_name: #type_name,
+            nested: #nested,
+        }
+    })
+}
+
+/// Tokens evaluating to the `&'static [FieldLayout]` of `fields`, which
+/// are packed one after another following `prefix` characters. Only the
+/// first field of a `separated` struct has a known offset.
+pub(crate) fn fields_layout(
+    prefix: usize,
+    fields: &[PackField],
+    separated: bool,
+) -> syn::Result<TokenStream2> {
+    let mut descriptors = Vec::new();
+    let mut sizes = vec![quote! { Some(#prefix) }];
+    for (index, field) in fields.iter().enumerate() {
+        let name = match &field.field.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        };
+        let offset = quote! { ::ascii_pack::sum_sizes(&[#(#sizes),*]) };
+        descriptors.push(field_layout(field, &name, offset)?);
+        sizes.push(match separated {
+            true => quote! { None },
+            false => size::field_size(field)?,
+        });
+    }
+
+    Ok(quote! { &[#(#descriptors),*] })
+}
+
+/// Tokens of the `FieldLayout` descriptor of an enum variant.
+pub(crate) fn variant_layout(
+    enum_ident: &syn::Ident,
+    variant: &syn::Ident,
+    size: TokenStream2,
+    text: Option<&str>,
+    nested: TokenStream2,
+) -> TokenStream2 {
+    let name = variant.to_string();
+    let type_name = enum_ident.to_string();
+    let text = match text {
+        Some(text) => quote! { Some(#text) },
+        None => quote! { None },
+    };
+
+    quote! {
+        ::ascii_pack::FieldLayout {
+            name: #name,
+            kind: ::ascii_pack::FieldKind::Variant,
+            size: #size,
+            offset: Some(0),
+            align: None,
+            pad: None,
+            text: #text,
+            type_name: #type_name,
+            nested: #nested,
+        }
+    }
+}
+
+/// Generates the `Layout` impl of a derived type, which exposes the
+/// `LAYOUT` of its `AsciiPack` impl.
+pub(crate) fn layout_impl(ident: &syn::Ident, generics: &syn::Generics) -> TokenStream2 {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::ascii_pack::Layout for #ident #ty_generics #where_clause {
+            fn layout() -> &'static [::ascii_pack::FieldLayout] {
+                <Self as ::ascii_pack::AsciiPack>::LAYOUT
+            }
+        }
+    }
+}
@@ -1,36 +1,325 @@
 use std::char;
 
-use darling::FromAttributes;
+use darling::util::Flag;
+use darling::{FromAttributes, FromMeta};
 use manyhow::manyhow;
-use proc_macro2::Span;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use proc_macro2::TokenTree;
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::*;
+use syn::parse_quote;
 use syn::spanned::Spanned;
-use syn::DeriveInput;
 use syn::Expr;
 use syn::Field;
+use syn::Path;
 use syn::Type;
-use syn::{parse::*, LitInt};
+use syn::{Attribute, DeriveInput, Meta};
+use value::{BoolTexts, Case, Sign, Value};
 
-#[derive(Debug, FromAttributes)]
+mod enums;
+mod layout;
+mod size;
+mod value;
+
+#[derive(Debug, Clone, FromAttributes)]
 #[darling(attributes(pack))]
 struct PackArgs {
-    size: Option<usize>,
+    size: Option<Expr>,
+    /// Number of digits of the length written before the value.
+    len_prefix: Option<u32>,
+    /// An earlier field holding the length of the value.
+    size_from: Option<Expr>,
+    /// Text ending the value, which may be quoted or escaped as
+    /// configured on the struct.
+    delimiter: Option<String>,
+    /// Condition on the earlier fields of `result` for the field to be
+    /// present, otherwise it is left at its default value.
+    when: Option<Expr>,
+    /// The character filling `Option` fields which are `None`.
+    blank: Option<char>,
+    /// Where the sign of a number is written.
+    sign: Option<Sign>,
+    /// Write a sign for positive numbers as well.
+    plus: Flag,
+    /// Number of digits after the implied decimal point.
+    scale: Option<u32>,
+    /// Number of digits written after the decimal point.
+    precision: Option<usize>,
+    /// The character written in place of the decimal point.
+    decimal: Option<char>,
+    /// The character written between each group of three whole digits.
+    grouping: Option<char>,
+    /// Base the number is written in.
+    radix: Option<u32>,
+    /// Case of the letter digits in a radix above 10.
+    case: Option<Case>,
+    /// Pattern of a date or time, such as `%Y%m%d`.
+    date: Option<String>,
+    /// Two digit years below the pivot are in the 2000s.
+    pivot: Option<u32>,
+    /// The texts of `true` and `false`.
+    bool: Option<BoolTexts>,
+    /// Match the texts of `true` and `false` without regard to ascii case.
+    ignore_case: Flag,
+    /// A module whose `from_ascii` and `to_ascii` functions convert the value.
+    with: Option<Path>,
+    /// The type the value is packed as, converted with `TryFrom`.
+    #[darling(skip, default = "no_as_type")]
+    as_type: Option<Type>,
     pad_left: Option<char>,
+    align: Option<Align>,
+    pad: Option<char>,
+    trim: Flag,
 }
 
-#[derive(Debug, FromAttributes)]
+impl PackArgs {
+    /// Whether the length of the value is given by the input
+    /// rather than by `size` or the `SIZE` of its type.
+    fn is_variable(&self) -> bool {
+        self.len_prefix.is_some() || self.size_from.is_some() || self.delimiter.is_some()
+    }
+
+    /// Whether the value is converted by a trait other than `AsciiPack`,
+    /// such as `ImpliedDecimal`, in which case its type need not implement
+    /// `AsciiPack` at all.
+    fn is_formatted(&self) -> bool {
+        self.scale.is_some()
+            || self.radix.is_some()
+            || self.date.is_some()
+            || self.bool.is_some()
+            || self.with.is_some()
+    }
+
+    /// The type packed into the field, which is the `as` type when given
+    /// and `T` for `Option<T>` fields.
+    fn packed_type<'a>(&'a self, field: &'a Field) -> &'a Type {
+        match &self.as_type {
+            Some(ty) => ty,
+            None => value::packed_type(field),
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromAttributes)]
 #[darling(attributes(pack_vec))]
 struct PackVecArgs {
-    until: Expr,
+    until: Option<Expr>,
+    /// An earlier field holding the number of items.
+    count: Option<Expr>,
+    /// Bounds on the number of items, checked both ways.
+    min: Option<usize>,
+    max: Option<usize>,
+    /// Pack `vec.len()` as the `count` field rather than its stored value.
+    rewrite: Flag,
+    /// A module whose `from_ascii` and `to_ascii` functions convert each item.
+    with: Option<Path>,
+    /// The type each item is packed as, converted with `TryFrom`.
+    #[darling(skip, default = "no_as_type")]
+    as_type: Option<Type>,
     pad_left: Option<char>,
-    size: Option<LitInt>, // TODO: don't require this
+    size: Option<Expr>,
+    align: Option<Align>,
+    pad: Option<char>,
+    trim: Flag,
+}
+
+impl PackVecArgs {
+    /// The type each item of a field of type `ty` is packed as, which is
+    /// the `as` type when given.
+    fn item_type(&self, ty: &Type) -> syn::Result<Type> {
+        match &self.as_type {
+            Some(as_type) => Ok(as_type.clone()),
+            None => extract_first_generic(ty),
+        }
+    }
+
+    /// The earlier field holding the number of items, when `count` is
+    /// a plain identifier.
+    fn count_field(&self) -> Option<&syn::Ident> {
+        match &self.count {
+            Some(Expr::Path(path)) => path.path.get_ident(),
+            _ => None,
+        }
+    }
+
+    /// The number of items when it is fixed, either by the length of an
+    /// array field or by a `count` which is not the name of a field, such
+    /// as `count = 12` or `count = { N }`.
+    fn fixed_count(&self, ty: &Type) -> Option<TokenStream2> {
+        if let Type::Array(array) = ty {
+            let len = &array.len;
+            return Some(quote! { (#len) });
+        }
+        match &self.count {
+            Some(count) if self.count_field().is_none() => Some(quote! { (#count) }),
+            _ => None,
+        }
+    }
+}
+
+/// Position of a value within a sized field, mirroring `ascii_pack::Align`.
+#[derive(Debug, Default, Clone, Copy, FromMeta)]
+#[darling(rename_all = "snake_case")]
+enum Align {
+    Left,
+    #[default]
+    Right,
+    Center,
+}
+
+impl ToTokens for Align {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.extend(match self {
+            Align::Left => quote! { ::ascii_pack::Align::Left },
+            Align::Right => quote! { ::ascii_pack::Align::Right },
+            Align::Center => quote! { ::ascii_pack::Align::Center },
+        });
+    }
+}
+
+/// What `from_ascii` accepts after the last field of a struct,
+/// mirroring `ascii_pack::Trailing`.
+#[derive(Debug, Default, Clone, Copy, FromMeta)]
+#[darling(rename_all = "snake_case")]
+enum Trailing {
+    #[default]
+    Error,
+    Ignore,
+    Whitespace,
+}
+
+impl ToTokens for Trailing {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.extend(match self {
+            Trailing::Error => quote! { ::ascii_pack::Trailing::Error },
+            Trailing::Ignore => quote! { ::ascii_pack::Trailing::Ignore },
+            Trailing::Whitespace => quote! { ::ascii_pack::Trailing::Whitespace },
+        });
+    }
+}
+
+/// How the value of a sized field is padded when packing, and
+/// whether that padding is trimmed again when unpacking.
+struct Padding {
+    align: Align,
+    /// Tokens evaluating to the pad character.
+    pad: TokenStream2,
+    trim: bool,
+}
+
+impl Padding {
+    /// Resolves the padding options of a field. `pad_left = c` is shorthand
+    /// for `align = "right", pad = c`, and values are right aligned and padded
+    /// with the `PAD` of their type `ty`, or `'0'` for formatted values,
+    /// unless specified otherwise.
+    fn new(
+        pad_left: Option<char>,
+        align: Option<Align>,
+        pad: Option<char>,
+        trim: &Flag,
+        field: &Field,
+        ty: Option<&Type>,
+    ) -> syn::Result<Padding> {
+        if pad_left.is_some() && (align.is_some() || pad.is_some()) {
+            return Err(syn::Error::new(
+                field.span(),
+                "pad_left cannot be combined with align or pad!",
+            ));
+        }
+
+        let align = align.unwrap_or_default();
+        let pad = pad_left.or(pad);
+        // digits padded after the value cannot be told apart from its own
+        // trailing digits, such as 120 padded to "1200"
+        if trim.is_present()
+            && pad.unwrap_or('0').is_ascii_digit()
+            && !matches!(align, Align::Right)
+        {
+            return Err(syn::Error::new(
+                field.span(),
+                "trim cannot be used with a digit pad after the value, which may end with the same digit!",
+            ));
+        }
+
+        let pad = match (pad, ty) {
+            (Some(pad), _) => quote! { #pad },
+            (None, Some(ty)) => quote! { <#ty as ::ascii_pack::AsciiPack>::PAD },
+            (None, None) => quote! { '0' },
+        };
+        Ok(Padding {
+            align,
+            pad,
+            trim: trim.is_present(),
+        })
+    }
+
+    /// Tokens evaluating to `text` with the padding trimmed when enabled.
+    fn trimmed(&self, text: TokenStream2) -> TokenStream2 {
+        let Padding { align, pad, trim } = self;
+        match trim {
+            true => quote! { #align.trim(#text, #pad) },
+            false => text,
+        }
+    }
+
+    /// Tokens evaluating to `text` of the field `name` padded to `size`
+    /// characters, returning the error of `pad_field` when it fails.
+    fn padded(&self, text: TokenStream2, size: &TokenStream2, name: &str) -> TokenStream2 {
+        let Padding { align, pad, .. } = self;
+        quote! { ::ascii_pack::pad_field(#align, #text, #size, #pad, #name)? }
+    }
 }
 
 #[derive(Debug, FromAttributes)]
+#[darling(attributes(pack_struct))]
+struct PackStructArgs {
+    /// Pack a struct with a single field exactly like that field.
+    transparent: Flag,
+    /// What `from_ascii` accepts after the last field.
+    trailing: Option<Trailing>,
+    /// Text separating every field, rather than fields having a fixed width.
+    separator: Option<String>,
+    /// Character which may surround delimited values.
+    quote: Option<char>,
+    /// Character which makes the following character of a delimited
+    /// value part of the value.
+    escape: Option<char>,
+}
+
+/// How a delimited field ends, mirroring `ascii_pack::Delimited`.
+#[derive(Debug, Clone)]
+struct Delimiter {
+    /// The text ending the field, or `None` when it ends with the input.
+    delimiter: Option<String>,
+    quote: Option<char>,
+    escape: Option<char>,
+}
+
+impl ToTokens for Delimiter {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let option = |value: Option<TokenStream2>| match value {
+            Some(value) => quote! { Some(#value) },
+            None => quote! { None },
+        };
+        let delimiter = option(self.delimiter.as_ref().map(|d| quote! { #d }));
+        let quote_char = option(self.quote.map(|q| quote! { #q }));
+        let escape = option(self.escape.map(|e| quote! { #e }));
+        tokens.extend(quote! {
+            ::ascii_pack::Delimited {
+                delimiter: #delimiter,
+                quote: #quote_char,
+                escape: #escape,
+            }
+        });
+    }
+}
+
+#[derive(Debug, Clone, FromAttributes)]
 #[darling(attributes(pack_static))]
 struct PackStaticArgs {
     text: String,
+    /// Skip over the text when unpacking without checking that it matches.
+    lenient: Flag,
 }
 
 impl Parse for PackVecArgs {
@@ -39,12 +328,196 @@ impl Parse for PackVecArgs {
     }
 }
 
+/// Describes where the generated code reads and writes the value of
+/// a field. Struct fields live on `result` and `self`, while the fields
+/// of an enum variant are bound to local variables.
+#[derive(Clone)]
+struct FieldTarget {
+    /// Place expression assigned to while unpacking, e.g. `result.name`.
+    unpack: TokenStream2,
+    /// Expression evaluating to a reference to the value while packing,
+    /// e.g. `&self.name`.
+    pack: TokenStream2,
+    /// Name of the field, used in error messages.
+    name: String,
+    /// For `pack_vec(count = ...)` and `pack(size_from = ...)` fields, the
+    /// target of the earlier field holding the number of items or the size.
+    length: Option<Box<FieldTarget>>,
+    /// How the field ends when it is delimited rather than sized.
+    delimiter: Option<Delimiter>,
+    /// For `pack(size_from = ...)` fields, the variable their text is packed
+    /// into before the earlier field holding its size.
+    text: Option<syn::Ident>,
+    /// Statements packing the text of later `size_from` fields, which run
+    /// before this field is packed as their size.
+    pack_first: TokenStream2,
+}
+
+impl FieldTarget {
+    /// Target for a field of the struct being derived.
+    /// Tuple struct fields are addressed by their `index`.
+    fn struct_field(field: &Field, index: usize) -> FieldTarget {
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(index.into()),
+        };
+        let name = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        };
+        FieldTarget {
+            unpack: quote! { result.#member },
+            pack: quote! { &self.#member },
+            name,
+            length: None,
+            delimiter: None,
+            text: None,
+            pack_first: TokenStream2::new(),
+        }
+    }
+
+    /// Target for a field of an enum variant, bound to `binding`
+    /// in both `from_ascii` and `to_ascii`.
+    fn binding(binding: &syn::Ident, name: String) -> FieldTarget {
+        FieldTarget {
+            unpack: quote! { #binding },
+            pack: quote! { #binding },
+            name,
+            length: None,
+            delimiter: None,
+            text: None,
+            pack_first: TokenStream2::new(),
+        }
+    }
+
+    /// Resolves the `count` of `pack_vec` fields and the `size_from` of
+    /// `pack` fields among `fields`, which must name an earlier field. That
+    /// field is packed as the number of items when `rewrite` is set, and as
+    /// the length of the packed text of the value for `size_from`, which is
+    /// packed ahead of it.
+    fn resolve_lengths(fields: &[PackField], targets: &mut [FieldTarget]) -> syn::Result<()> {
+        for (index, PackField { field, attr }) in fields.iter().enumerate() {
+            let pack = &targets[index].pack;
+            let (length, rewritten, text) = match attr {
+                FieldAttr::PackVec(args) => match args.count_field() {
+                    Some(count) => (
+                        count.clone(),
+                        args.rewrite.is_present().then(|| quote! { (#pack).len() }),
+                        None,
+                    ),
+                    None => continue,
+                },
+                FieldAttr::Pack(args) => match &args.size_from {
+                    Some(size_from) => {
+                        let var = format_ident!("size_from_text_{}", index);
+                        let substr =
+                            Value::new(args, field)?.pack(pack, None, &targets[index].name);
+                        (
+                            field_ident(size_from, "size_from")?.clone(),
+                            Some(quote! { #var.len() }),
+                            Some((var, substr)),
+                        )
+                    }
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            let earlier = fields[..index]
+                .iter()
+                .position(|earlier| earlier.field.ident.as_ref() == Some(&length));
+            let Some(earlier) = earlier else {
+                return Err(syn::Error::new(
+                    length.span(),
+                    "The length must be taken from an earlier field of the same struct or variant!",
+                ));
+            };
+
+            let mut length_target = targets[earlier].clone();
+            length_target.length = None;
+            targets[index].length = Some(Box::new(length_target));
+
+            if let Some((var, substr)) = text {
+                targets[earlier]
+                    .pack_first
+                    .extend(quote! { let #var = #substr; });
+                targets[index].text = Some(var);
+            }
+            if let Some(rewritten) = rewritten {
+                let ty = &fields[earlier].field.ty;
+                let lit_name = targets[earlier].name.clone();
+                targets[earlier].pack = quote! {
+                    &<#ty as ::core::convert::TryFrom<usize>>::try_from(#rewritten).map_err(|_| {
+                        ::ascii_pack::AsciiPackError::Pack(
+                            format!("The length does not fit in {}", #lit_name))
+                    })?
+                };
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FieldTarget {
+    /// Resolves how each of `fields` is delimited. Fields with a `delimiter`
+    /// end with it, and with a `separator`, every other field ends with the
+    /// separator, apart from the last field which ends with the input.
+    fn resolve_delimiters(
+        fields: &[PackField],
+        targets: &mut [FieldTarget],
+        separator: Option<&String>,
+        quote: Option<char>,
+        escape: Option<char>,
+    ) -> syn::Result<()> {
+        let last = fields
+            .iter()
+            .rposition(|field| !matches!(field.attr, FieldAttr::Ignore));
+
+        for (index, PackField { field, attr }) in fields.iter().enumerate() {
+            let delimiter = match (attr, separator) {
+                (FieldAttr::Ignore, _) => continue,
+                (FieldAttr::Pack(args), _) if args.delimiter.is_some() => args.delimiter.clone(),
+                (FieldAttr::PackVec(_), Some(_)) => {
+                    return Err(syn::Error::new(
+                        field.span(),
+                        "pack_vec fields cannot be used in structs with a separator!",
+                    ))
+                }
+                (_, Some(_)) if Some(index) == last => None,
+                (_, Some(separator)) => Some(separator.clone()),
+                (_, None) => continue,
+            };
+
+            if delimiter.as_deref() == Some("") {
+                return Err(syn::Error::new(field.span(), "Delimiters cannot be empty!"));
+            }
+            targets[index].delimiter = Some(Delimiter {
+                delimiter,
+                quote,
+                escape,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Extracts the name of the field referenced by the `option` of a field.
+fn field_ident<'a>(expr: &'a Expr, option: &str) -> syn::Result<&'a syn::Ident> {
+    match expr {
+        Expr::Path(path) => path.path.get_ident(),
+        _ => None,
+    }
+    .ok_or_else(|| syn::Error::new(expr.span(), format!("{} must name a field!", option)))
+}
+
 /// Utility function to extract the type `T` from a single-type
-/// generic such as `Vec<T>`. This assumes that the field is defined
-/// literally as `Vec<T>`, with no type aliasing, as a type aliased
-/// generic (`type MyVec = Vec<T>;`) will not work with this logic.
+/// generic such as `Vec<T>`, or from an array `[T; N]`. This assumes
+/// that the field is defined literally as `Vec<T>`, with no type aliasing,
+/// as a type aliased generic (`type MyVec = Vec<T>;`) will not work with
+/// this logic.
 fn extract_first_generic(ty: &Type) -> syn::Result<Type> {
     match ty {
+        syn::Type::Array(array) => Ok((*array.elem).clone()),
         syn::Type::Path(type_path) => {
             let generics: std::result::Result<&syn::PathSegment, syn::Error> =
                 match type_path.path.segments.first() {
@@ -65,7 +538,7 @@ fn extract_first_generic(ty: &Type) -> syn::Result<Type> {
                     .filter(|t| t.is_some())
                     .flatten()
                     .last(),
-                syn::PathArguments::Parenthesized(paren) => paren.inputs.first().clone().cloned(),
+                syn::PathArguments::Parenthesized(paren) => paren.inputs.first().cloned(),
             };
 
             match generic_type {
@@ -83,69 +556,160 @@ fn extract_first_generic(ty: &Type) -> syn::Result<Type> {
     }
 }
 
+/// Returns whether `ty` mentions any of the type parameters in `generics`.
+fn uses_type_params(ty: &Type, generics: &syn::Generics) -> bool {
+    fn visit(tokens: TokenStream2, params: &[&syn::Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => params.contains(&&ident),
+            TokenTree::Group(group) => visit(group.stream(), params),
+            _ => false,
+        })
+    }
+
+    let params: Vec<&syn::Ident> = generics.type_params().map(|param| &param.ident).collect();
+    !params.is_empty() && visit(ty.to_token_stream(), &params)
+}
+
+/// Adds the `AsciiPack` and `AsciiPackUnsized` bounds required by the
+/// generated code to the where clause of `generics`, for every field whose
+/// type depends on a type parameter. Bounds on concrete types are left to
+/// the compiler to check where they are used.
+fn add_field_bounds<'a: 'b, 'b>(
+    generics: &mut syn::Generics,
+    fields: impl Iterator<Item = &'b PackField<'a>>,
+) -> syn::Result<()> {
+    let mut predicates: Vec<syn::WherePredicate> = Vec::new();
+    for PackField { field, attr } in fields {
+        let (ty, is_sized) = match attr {
+            FieldAttr::Pack(args) => {
+                // Option fields always have a size, so their values are never unsized
+                let is_sized = args.size.is_some()
+                    || args.is_variable()
+                    || value::packed_type(field) != &field.ty;
+                if args.is_formatted() {
+                    continue;
+                }
+                (args.packed_type(field).clone(), is_sized)
+            }
+            FieldAttr::PackVec(args) => {
+                if args.with.is_some() {
+                    continue;
+                }
+                (args.item_type(&field.ty)?, args.size.is_some())
+            }
+            _ => continue,
+        };
+
+        if !uses_type_params(&ty, generics) {
+            continue;
+        }
+
+        predicates.push(parse_quote! { #ty: ::ascii_pack::AsciiPack });
+        if !is_sized {
+            predicates.push(parse_quote! { #ty: ::ascii_pack::AsciiPackUnsized });
+        }
+    }
+
+    if !predicates.is_empty() {
+        generics.make_where_clause().predicates.extend(predicates);
+    }
+    Ok(())
+}
+
 /// Generates the `to_ascii` and `from_ascii` tokens
-/// for pack_vec fields
+/// for pack fields
 fn generate_pack_tokens(
     mut from_ascii_tokens: TokenStream2,
     mut to_ascii_tokens: TokenStream2,
     args: PackArgs,
     field: &Field,
+    target: &FieldTarget,
 ) -> syn::Result<(TokenStream2, TokenStream2)> {
-    let name = &field.ident.clone().unwrap();
-    let ty = &field.ty;
-    let lit_name = name.to_string();
+    if let Some(delimiter) = &target.delimiter {
+        return generate_delimited_tokens(
+            from_ascii_tokens,
+            to_ascii_tokens,
+            args,
+            field,
+            target,
+            delimiter,
+        );
+    }
+    if args.is_variable() {
+        return generate_variable_pack_tokens(
+            from_ascii_tokens,
+            to_ascii_tokens,
+            args,
+            field,
+            target,
+        );
+    }
 
-    let size = args.size;
-    //let size = size_lit.base10_parse::<usize>().unwrap();
-    //let right_bound = left_bound + size - 1;
+    let value = Value::new(&args, field)?;
+    let ty = value.ty();
+    let unpack = &target.unpack;
+    let pack = &target.pack;
+    let lit_name = &target.name;
 
-    let pad_left = args.pad_left.unwrap_or('0');
+    let size = args.size.map(|size| quote! { (#size) });
 
-    match size {
+    match &size {
         Some(size_lit) => {
+            let slice = value.unpack(quote! { slice });
             from_ascii_tokens = quote! {
                 #from_ascii_tokens
-                result.#name = #ty::from_ascii(&input[left_bound..=(left_bound + #size_lit - 1)])?;
+                let slice = ::ascii_pack::field_slice(input, #lit_name, left_bound, #size_lit)?;
+                #unpack = #slice;
                 left_bound += #size_lit;
             };
         }
+        None if value.is_encoded() => {
+            return Err(syn::Error::new(
+                field.span(),
+                "Option, sign and formatted fields require a size, a delimiter or a length prefix!",
+            ))
+        }
+        // the size may be omitted for types with a known SIZE
         None => {
+            let field_value = value.converted(quote! { field_value });
             from_ascii_tokens = quote! {
                 #from_ascii_tokens
-                let (field_value, calculated_size) = #ty::from_ascii_unsized(&input[left_bound..])?;
-                result.#name = field_value;
-                left_bound += calculated_size;
+                match <#ty as ::ascii_pack::AsciiPack>::SIZE {
+                    Some(size) => {
+                        let slice = ::ascii_pack::field_slice(input, #lit_name, left_bound, size)?;
+                        let field_value = <#ty as ::ascii_pack::AsciiPack>::from_ascii(slice)?;
+                        #unpack = #field_value;
+                        left_bound += size;
+                    }
+                    None => {
+                        let rest = ::ascii_pack::field_rest(input, #lit_name, left_bound)?;
+                        let (field_value, calculated_size) = <#ty as ::ascii_pack::AsciiPackUnsized>::from_ascii_unsized(rest)?;
+                        #unpack = #field_value;
+                        left_bound += calculated_size;
+                    }
+                }
             };
         }
     }
 
     match size {
         Some(size) => {
+            let substr = value.pack(pack, Some(&size), lit_name);
             to_ascii_tokens = quote! {
                 #to_ascii_tokens
-                let mut substr = &self.#name.to_ascii()?;
+                let substr = #substr;
                 if substr.len() > #size {
-                    return Err(AsciiPackError::Pack(
+                    return Err(::ascii_pack::AsciiPackError::Pack(
                         format!("Size of item in {} was too large - item: {}, expected size: {}", #lit_name, substr, #size)));
                 }
-                let padding_size = #size - substr.len();
-                if padding_size > 0 {
-                    let mut pad_str = String::new();
-                    for _ in 0..padding_size {
-                        pad_str.push(#pad_left);
-                    }
-                    pad_str.push_str(&substr);
-                    result.push_str(&pad_str)
-                } else {
-                    result.push_str(&substr);
-                }
+                result.push_str(&substr);
             };
         }
         None => {
+            let packed = value.packed(pack);
             to_ascii_tokens = quote! {
                 #to_ascii_tokens
-                let mut substr = &self.#name.to_ascii()?;
-                result.push_str(&substr);
+                result.push_str(&::ascii_pack::AsciiPack::to_ascii(#packed)?);
             };
         }
     };
@@ -153,253 +717,1346 @@ fn generate_pack_tokens(
     Ok((from_ascii_tokens, to_ascii_tokens))
 }
 
-/// Generates the `to_ascii` and `from_ascii` tokens
-/// for pack_vec fields
-fn generate_pack_vec_tokens(
+/// Generates the `to_ascii` and `from_ascii` tokens for delimited pack
+/// fields. A declared size pads the value within the delimiters.
+fn generate_delimited_tokens(
     mut from_ascii_tokens: TokenStream2,
     mut to_ascii_tokens: TokenStream2,
-    args: PackVecArgs,
+    args: PackArgs,
     field: &Field,
+    target: &FieldTarget,
+    delimiter: &Delimiter,
 ) -> syn::Result<(TokenStream2, TokenStream2)> {
-    let ty = &field.ty;
-    let generic_type = extract_first_generic(&ty)?;
-    let name = &field.ident.clone().unwrap();
-    let until = args.until;
-    let has_size = args.size.is_some();
-    let size = &args.size.unwrap_or(LitInt::new("99999", Span::call_site()));
-    let pad_left = args.pad_left.unwrap_or('0');
-    let lit_name = name.to_string();
+    let value = Value::new(&args, field)?;
+    let unpack = &target.unpack;
+    let pack = &target.pack;
+    let lit_name = &target.name;
 
-    // TODO: this cannot be a fixed size, so we cannot use from_str here.
-    // Instead, we must impl AsciiPack for primitives and then consume the buffer as necessary.
+    if args.len_prefix.is_some() || args.size_from.is_some() {
+        return Err(syn::Error::new(
+            field.span(),
+            "len_prefix and size_from cannot be used on delimited fields!",
+        ));
+    }
 
+    let text = value.unpack(quote! { &text });
     from_ascii_tokens = quote! {
         #from_ascii_tokens
-        let stop_fn = #until;
-        let mut slice = match #has_size {
-            true => &input[left_bound..=(left_bound + #size - 1).min(input.len() - 1)],
-            false => &input[left_bound..]
-        };
-        while !stop_fn(&slice) {
-            let value = #generic_type::from_ascii(&slice)?;
-            match #has_size {
-                true => {left_bound += #size;},
-                false => {
-                    println!("parsing: {}", slice);
-                    left_bound += value.to_ascii()?.len();
-                }
-            }
-            let size_used = value.to_ascii()?.len();
-            result.#name.push(value);
-            slice = match #has_size {
-                true => &input[left_bound..=(left_bound + #size - 1).min(input.len() - 1)],
-                false => &input[left_bound..]
-            };
-        }
+        let rest = ::ascii_pack::field_rest(input, #lit_name, left_bound)?;
+        let (text, consumed) = #delimiter.read(rest)?;
+        #unpack = #text;
+        left_bound += consumed;
     };
 
-    to_ascii_tokens = quote! {
-        #to_ascii_tokens
-        for item in &self.#name {
-            let mut substr = item.to_ascii()?;
+    let size = args.size.map(|size| quote! { (#size) });
+    let substr = value.pack(pack, size.as_ref(), lit_name);
+    let check = size.map(|size| {
+        quote! {
             if substr.len() > #size {
-                return Err(AsciiPackError::Pack(
+                return Err(::ascii_pack::AsciiPackError::Pack(
                     format!("Size of item in {} was too large - item: {}, expected size: {}", #lit_name, substr, #size)));
             }
-
-            if !#has_size {
-                result.push_str(&substr);
-                continue;
-            }
-
-            let padding_size = #size - substr.len();
-            if padding_size > 0 {
-                let mut pad_str = String::new();
-                for _ in 0..padding_size {
-                    pad_str.push(#pad_left);
-                }
-                pad_str.push_str(&substr);
-                result.push_str(&pad_str)
-            } else {
-                result.push_str(&substr);
-            }
         }
+    });
+    to_ascii_tokens = quote! {
+        #to_ascii_tokens
+        let substr = #substr;
+        #check
+        #delimiter.write(&substr, &mut result)?;
     };
 
     Ok((from_ascii_tokens, to_ascii_tokens))
 }
 
-/// Generates the `to_ascii` and `from_ascii` tokens
-/// for pack_static fields
-fn generate_pack_static_tokens(
+/// Generates the `to_ascii` and `from_ascii` tokens for pack fields whose
+/// length is given by the input, either by `len_prefix` digits written before
+/// the value or by the earlier `size_from` field.
+fn generate_variable_pack_tokens(
     mut from_ascii_tokens: TokenStream2,
     mut to_ascii_tokens: TokenStream2,
-    args: PackStaticArgs,
+    args: PackArgs,
+    field: &Field,
+    target: &FieldTarget,
 ) -> syn::Result<(TokenStream2, TokenStream2)> {
-    let static_value = args.text;
-    let size = static_value.len();
+    let value = Value::new(&args, field)?;
+    let unpack = &target.unpack;
+    let pack = &target.pack;
+    let lit_name = &target.name;
+
+    if args.size.is_some()
+        || args.pad_left.is_some()
+        || args.align.is_some()
+        || args.pad.is_some()
+        || args.trim.is_present()
+    {
+        return Err(syn::Error::new(
+            field.span(),
+            "len_prefix and size_from cannot be combined with size or padding!",
+        ));
+    }
+
+    let (read_len, write_len) = match (args.len_prefix, &target.length) {
+        (Some(digits), None) => {
+            let digits_len = digits as usize;
+            let read_len = quote! {
+                let prefix = ::ascii_pack::field_slice(input, #lit_name, left_bound, #digits_len)?;
+                if !prefix.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(::ascii_pack::AsciiPackError::Unpack(
+                        format!("The length prefix of {} is not a number: '{}'", #lit_name, prefix)));
+                }
+                let len: usize = prefix.parse()?;
+                left_bound += #digits_len;
+            };
+            // prefixes of 20 or more digits can hold any length
+            let overflow = 10usize.checked_pow(digits).map(|limit| {
+                quote! {
+                    if substr.len() >= #limit {
+                        return Err(::ascii_pack::AsciiPackError::Pack(format!(
+                            "{} is {} characters long, which does not fit in a length prefix of {} digits",
+                            #lit_name, substr.len(), #digits)));
+                    }
+                }
+            });
+            let write_len = quote! {
+                #overflow
+                result.push_str(&format!("{:0width$}", substr.len(), width = #digits_len));
+            };
+            (read_len, write_len)
+        }
+        // the length field itself is packed as the length of the value
+        (None, Some(length)) => {
+            let length_unpack = &length.unpack;
+            let length_name = &length.name;
+            let read_len = quote! {
+                let len = usize::try_from(#length_unpack).map_err(|_| {
+                    ::ascii_pack::AsciiPackError::Unpack(
+                        format!("{} is not a valid size for {}", #length_name, #lit_name))
+                })?;
+            };
+            (read_len, TokenStream2::new())
+        }
+        _ => {
+            return Err(syn::Error::new(
+                field.span(),
+                "len_prefix and size_from cannot be combined!",
+            ))
+        }
+    };
 
+    let slice = value.unpack(quote! { slice });
     from_ascii_tokens = quote! {
         #from_ascii_tokens
-        // no need to set the field - default will be fine.
-        left_bound += #size;
+        #read_len
+        let slice = ::ascii_pack::field_slice(input, #lit_name, left_bound, len)?;
+        #unpack = #slice;
+        left_bound += len;
     };
 
+    // the text of size_from fields is packed ahead of their size
+    let substr = match &target.text {
+        Some(text) => quote! { #text },
+        None => value.pack(pack, None, lit_name),
+    };
     to_ascii_tokens = quote! {
         #to_ascii_tokens
-        // push the static string value onto the output.
-        result.push_str(#static_value);
+        let substr = #substr;
+        #write_len
+        result.push_str(&substr);
     };
 
     Ok((from_ascii_tokens, to_ascii_tokens))
 }
 
-/// Process the given field and output the to_ascii
-/// and from_ascii tokens.
-///
-/// Note: this Field may include attributes from other macros
-/// invoked by the user that are not relevant to AsciiPack.
-fn process_field(
+/// Generates the `to_ascii` and `from_ascii` tokens
+/// for pack_vec fields
+fn generate_pack_vec_tokens(
     mut from_ascii_tokens: TokenStream2,
     mut to_ascii_tokens: TokenStream2,
+    args: PackVecArgs,
     field: &Field,
+    target: &FieldTarget,
 ) -> syn::Result<(TokenStream2, TokenStream2)> {
-    let mut already_parsed = false;
-    for attr in field.attrs.iter() {
-        let name = attr.meta.path().require_ident()?.to_string();
-        let matched = match name.as_str() {
-            "pack_ignore" => {
-                // leave the default value
-                true
-            }
-            "pack" => {
-                let args: PackArgs = FromAttributes::from_attributes(&field.attrs)?;
-                let (from, to) =
-                    generate_pack_tokens(from_ascii_tokens, to_ascii_tokens, args, &field)?;
-                from_ascii_tokens = from;
-                to_ascii_tokens = to;
-                true
+    let ty = &field.ty;
+    let generic_type = extract_first_generic(ty)?;
+    let item_type = args.item_type(ty)?;
+    let unpack = &target.unpack;
+    let pack = &target.pack;
+    let size = args.size.as_ref().map(|size| quote! { (#size) });
+    let padding = Padding::new(
+        args.pad_left,
+        args.align,
+        args.pad,
+        &args.trim,
+        field,
+        args.with.is_none().then_some(&item_type),
+    )?;
+    let lit_name = &target.name;
+
+    // items are given their index as context, relative to the field start
+    let item_type_name = layout::type_name(&generic_type);
+    let item_context = |size: TokenStream2| {
+        quote! {
+            e.context(
+                ::ascii_pack::PathSegment::Index(index),
+                input.get(field_start..).unwrap_or_default(),
+                left_bound - field_start,
+                #size,
+                #item_type_name,
+            )
+        }
+    };
+    // arrays are filled in place, while vectors are pushed onto
+    let is_array = matches!(ty, Type::Array(_));
+    let store = match is_array {
+        true => quote! { #unpack[index] = value; },
+        false => quote! { #unpack.push(value); },
+    };
+    // items packed as another type are converted after being unpacked
+    let convert = |context: &TokenStream2| {
+        args.as_type.as_ref().map(|as_type| {
+            quote! {
+                let value = ::ascii_pack::convert_from::<#as_type, #generic_type>(value)
+                    .map_err(|e| #context)?;
             }
-            "pack_vec" => {
-                let args: PackVecArgs = FromAttributes::from_attributes(&field.attrs)?;
-                let (from, to) =
-                    generate_pack_vec_tokens(from_ascii_tokens, to_ascii_tokens, args, &field)?;
-                from_ascii_tokens = from;
-                to_ascii_tokens = to;
-                true
+        })
+    };
+    let read_item = match &args.with {
+        Some(with) => quote! { #with::from_ascii },
+        None => quote! { <#item_type as ::ascii_pack::AsciiPack>::from_ascii },
+    };
+    let trimmed = padding.trimmed(quote! { slice });
+    let (item, chunk) = match &size {
+        Some(size) => {
+            let context = item_context(quote! { Some(#size) });
+            let convert = convert(&context);
+            let item = quote! {
+                let slice = ::ascii_pack::field_slice(input, #lit_name, left_bound, #size)?;
+                let value = #read_item(#trimmed).map_err(|e| #context)?;
+                #convert
+                left_bound += #size;
+                #store
+            };
+            // the final chunk passed to the until function may be short,
+            // but every item which is unpacked must be complete.
+            let chunk = quote! { ::ascii_pack::field_chunk(input, #lit_name, left_bound, #size)? };
+            (item, chunk)
+        }
+        None if args.with.is_some() => {
+            return Err(syn::Error::new(
+                field.span(),
+                "pack_vec fields with a with module require a size!",
+            ))
+        }
+        // unsized items report how much of the input they consumed
+        None => {
+            let context = item_context(quote! { None });
+            let convert = convert(&context);
+            let item = quote! {
+                let rest = ::ascii_pack::field_rest(input, #lit_name, left_bound)?;
+                let (value, calculated_size) = <#item_type as ::ascii_pack::AsciiPackUnsized>::from_ascii_unsized(rest)
+                    .map_err(|e| #context)?;
+                #convert
+                left_bound += calculated_size;
+                #store
+            };
+            let chunk = quote! { ::ascii_pack::field_rest(input, #lit_name, left_bound)? };
+            (item, chunk)
+        }
+    };
+
+    let check_bounds = |len: TokenStream2, error: TokenStream2| {
+        let min = args.min.map(|min| {
+            quote! {
+                if #len < #min {
+                    return Err(::ascii_pack::AsciiPackError::#error(format!(
+                        "{} has {} items, but at least {} are required", #lit_name, #len, #min)));
+                }
             }
-            "pack_static" => {
-                let args: PackStaticArgs = FromAttributes::from_attributes(&field.attrs)?;
-                let (from, to) =
-                    generate_pack_static_tokens(from_ascii_tokens, to_ascii_tokens, args)?;
-                from_ascii_tokens = from;
-                to_ascii_tokens = to;
-                true
+        });
+        let max = args.max.map(|max| {
+            quote! {
+                if #len > #max {
+                    return Err(::ascii_pack::AsciiPackError::#error(format!(
+                        "{} has {} items, but at most {} are allowed", #lit_name, #len, #max)));
+                }
             }
-            _ => false, // attribute not relevant to ascii pack
-        };
+        });
+        quote! { #min #max }
+    };
 
-        if matched && already_parsed {
+    let fixed_count = args.fixed_count(ty);
+    if is_array && (args.count.is_some() || args.until.is_some()) {
+        return Err(syn::Error::new(
+            field.span(),
+            "The number of items of an array is fixed by its length!",
+        ));
+    }
+
+    from_ascii_tokens = match (&args.until, &target.length, &fixed_count) {
+        // each item must consume something for the loop to make progress
+        (Some(until), None, None) => {
+            let bounds = check_bounds(quote! { #unpack.len() }, quote! { Unpack });
+            quote! {
+                #from_ascii_tokens
+                let stop_fn = #until;
+                while !stop_fn(#chunk) {
+                    let index = #unpack.len();
+                    let item_start = left_bound;
+                    #item
+                    if left_bound == item_start {
+                        return Err(::ascii_pack::AsciiPackError::Unpack(
+                            format!("An item of {} consumed no input at offset {}", #lit_name, item_start)));
+                    }
+                }
+                #bounds
+            }
+        }
+        (None, None, Some(count)) => quote! {
+            #from_ascii_tokens
+            for index in 0..#count {
+                #item
+            }
+        },
+        (None, Some(count), None) => {
+            let count_unpack = &count.unpack;
+            let count_name = &count.name;
+            let bounds = check_bounds(quote! { count }, quote! { Unpack });
+            quote! {
+                #from_ascii_tokens
+                let count = usize::try_from(#count_unpack).map_err(|_| {
+                    ::ascii_pack::AsciiPackError::Unpack(
+                        format!("{} is not a valid number of items for {}", #count_name, #lit_name))
+                })?;
+                #bounds
+                for index in 0..count {
+                    #item
+                }
+            }
+        }
+        _ => {
             return Err(syn::Error::new(
                 field.span(),
-                "Only one AsciiPack attribute is allowed on all fields!",
-            ));
+                "pack_vec requires exactly one of until or count!",
+            ))
         }
+    };
 
-        if matched {
-            already_parsed = true;
+    let pack_item = match &size {
+        Some(size) => {
+            let padded = padding.padded(quote! { &substr }, size, lit_name);
+            quote! {
+                if substr.len() > #size {
+                    return Err(::ascii_pack::AsciiPackError::Pack(
+                        format!("Size of item in {} was too large - item: {}, expected size: {}", #lit_name, substr, #size)));
+                }
+                result.push_str(&#padded);
+            }
         }
-    }
+        None => quote! {
+            result.push_str(&substr);
+        },
+    };
+
+    let write_item = match &args.with {
+        Some(with) => quote! { #with::to_ascii },
+        None => quote! { ::ascii_pack::AsciiPack::to_ascii },
+    };
+    let packed_item = match &args.as_type {
+        Some(as_type) => {
+            quote! { &::ascii_pack::convert_into::<#generic_type, #as_type>(item)? }
+        }
+        None => quote! { item },
+    };
 
-    match already_parsed {
-        true => Ok((from_ascii_tokens, to_ascii_tokens)),
-        false => Err(syn::Error::new(
+    let bounds = check_bounds(quote! { (#pack).len() }, quote! { Pack });
+    let count_check = match &target.length {
+        Some(count) if !args.rewrite.is_present() => {
+            let count_pack = &count.pack;
+            let count_name = &count.name;
+            quote! {
+                if usize::try_from(*#count_pack).ok() != Some((#pack).len()) {
+                    return Err(::ascii_pack::AsciiPackError::Pack(format!(
+                        "{} does not match the {} items of {}", #count_name, (#pack).len(), #lit_name)));
+                }
+            }
+        }
+        // arrays always hold the right number of items
+        _ => match &fixed_count {
+            Some(count) if !is_array => quote! {
+                if (#pack).len() != #count {
+                    return Err(::ascii_pack::AsciiPackError::Pack(format!(
+                        "{} has {} items, but exactly {} are required", #lit_name, (#pack).len(), #count)));
+                }
+            },
+            _ => TokenStream2::new(),
+        },
+    };
+    if args.rewrite.is_present() && args.count_field().is_none() {
+        return Err(syn::Error::new(
             field.span(),
-            "At least one AsciiPack attribute is required on all fields!",
-        )),
+            "rewrite can only be used along with a count field!",
+        ));
     }
+
+    to_ascii_tokens = quote! {
+        #to_ascii_tokens
+        #bounds
+        #count_check
+        for item in #pack {
+            let substr = #write_item(#packed_item)?;
+            #pack_item
+        }
+    };
+
+    Ok((from_ascii_tokens, to_ascii_tokens))
 }
 
-/// This macro is used to derive ascii format packing metadata and relevant functions to
-/// pack and unpack structured, sized data from strongly sized ascii formats into native
-/// rust types, bidirectionally.
-///
-/// Example:
-///
-/// ```
-/// const TEST_ASCII: &str = "  EXAMPLETESTTESTTEST00120654012346543345delimeterabc";
-///
-/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
-/// pub struct VecTest {
-///     #[pack(size = 9, pad_left = ' ')]
-///     pub string1: String,
-///
-///     #[pack_vec(size = 4, until = until::ascii_digit)]
-///     pub string_vec: Vec<String>,
-///
-///     #[pack_vec(size = 4, until = until::starts_with("del"))]
-///     pub usize_vec: Vec<usize>,
-///
-///     #[pack(size = 9)]
-///     pub delimeter: String,
-///
-///     #[pack_vec(size = 1, until = until::empty)]
-///     pub trailing_vec: Vec<char>,
-/// }
-/// ```
-#[manyhow(proc_macro_derive(AsciiPack, attributes(pack, pack_ignore, pack_vec, pack_static)))]
-pub fn derive_ascii_pack(item: proc_macro::TokenStream) -> syn::Result<proc_macro::TokenStream> {
-    let input = syn::parse::<DeriveInput>(item)?;
-    let data = match input.data {
-        syn::Data::Struct(s) => s,
-        syn::Data::Enum(_) => panic!("not supported for enums"),
-        syn::Data::Union(_) => panic!("not supported for unions"),
+/// Generates the `to_ascii` and `from_ascii` tokens
+/// for pack_static fields
+fn generate_pack_static_tokens(
+    mut from_ascii_tokens: TokenStream2,
+    mut to_ascii_tokens: TokenStream2,
+    args: PackStaticArgs,
+    target: &FieldTarget,
+) -> syn::Result<(TokenStream2, TokenStream2)> {
+    let static_value = args.text;
+    let size = static_value.len();
+
+    // in separated structs, the text is a field of its own
+    if let Some(delimiter) = &target.delimiter {
+        let lit_name = &target.name;
+        let check = match args.lenient.is_present() {
+            true => TokenStream2::new(),
+            false => quote! {
+                if text != #static_value {
+                    return Err(::ascii_pack::AsciiPackError::StaticMismatch {
+                        expected: #static_value,
+                        found: text,
+                        offset: left_bound,
+                    });
+                }
+            },
+        };
+        from_ascii_tokens = quote! {
+            #from_ascii_tokens
+            let rest = ::ascii_pack::field_rest(input, #lit_name, left_bound)?;
+            let (text, consumed) = #delimiter.read(rest)?;
+            #check
+            left_bound += consumed;
+        };
+        to_ascii_tokens = quote! {
+            #to_ascii_tokens
+            #delimiter.write(#static_value, &mut result)?;
+        };
+        return Ok((from_ascii_tokens, to_ascii_tokens));
+    }
+
+    if !args.lenient.is_present() {
+        from_ascii_tokens = quote! {
+            #from_ascii_tokens
+            if input.get(left_bound..left_bound + #size) != Some(#static_value) {
+                return Err(::ascii_pack::AsciiPackError::StaticMismatch {
+                    expected: #static_value,
+                    found: input.get(left_bound..).unwrap_or_default().chars().take(#size).collect(),
+                    offset: left_bound,
+                });
+            }
+        };
+    }
+
+    from_ascii_tokens = quote! {
+        #from_ascii_tokens
+        // no need to set the field - default will be fine.
+        left_bound += #size;
+    };
+
+    to_ascii_tokens = quote! {
+        #to_ascii_tokens
+        // push the static string value onto the output.
+        result.push_str(#static_value);
     };
 
-    let struc = input.ident;
+    Ok((from_ascii_tokens, to_ascii_tokens))
+}
+
+/// The AsciiPack attribute of a field, along with its parsed arguments.
+#[derive(Clone)]
+enum FieldAttr {
+    Ignore,
+    Pack(Box<PackArgs>),
+    PackVec(Box<PackVecArgs>),
+    PackStatic(PackStaticArgs),
+}
+
+impl FieldAttr {
+    /// Parses the single AsciiPack attribute of the given field.
+    ///
+    /// Note: this Field may include attributes from other macros
+    /// invoked by the user that are not relevant to AsciiPack.
+    fn parse(field: &Field) -> syn::Result<FieldAttr> {
+        let mut parsed = None;
+        for attr in field.attrs.iter() {
+            let name = attr.meta.path().require_ident()?.to_string();
+            let matched = match name.as_str() {
+                "pack_ignore" => FieldAttr::Ignore,
+                "pack" => {
+                    let (mut args, as_type) = parse_args::<PackArgs>(&field.attrs, "pack")?;
+                    args.as_type = as_type;
+                    FieldAttr::Pack(Box::new(args))
+                }
+                "pack_vec" => {
+                    let (mut args, as_type) = parse_args::<PackVecArgs>(&field.attrs, "pack_vec")?;
+                    args.as_type = as_type;
+                    FieldAttr::PackVec(Box::new(args))
+                }
+                "pack_static" => {
+                    FieldAttr::PackStatic(PackStaticArgs::from_attributes(&field.attrs)?)
+                }
+                _ => continue, // attribute not relevant to ascii pack
+            };
+
+            if parsed.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "Only one AsciiPack attribute is allowed on all fields!",
+                ));
+            }
+            parsed = Some(matched);
+        }
+
+        match parsed {
+            Some(parsed) => Ok(parsed),
+            None => Err(syn::Error::new(
+                field.span(),
+                "At least one AsciiPack attribute is required on all fields!",
+            )),
+        }
+    }
+}
+
+/// A field of a derived struct or variant along with its AsciiPack
+/// attribute, which is parsed once and shared by every part of the derive.
+struct PackField<'a> {
+    field: &'a Field,
+    attr: FieldAttr,
+}
+
+impl PackField<'_> {
+    /// Parses the AsciiPack attribute of every field in `fields`.
+    fn parse_all(fields: &syn::Fields) -> syn::Result<Vec<PackField<'_>>> {
+        fields
+            .iter()
+            .map(|field| {
+                Ok(PackField {
+                    field,
+                    attr: FieldAttr::parse(field)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Parses the arguments of the `name` attributes among `attrs`, along with
+/// the type of their `as` option. `as` is a keyword, which darling cannot
+/// parse as the name of an option, so it is parsed here and removed from
+/// the options handed to darling.
+fn parse_args<T: FromAttributes>(
+    attrs: &[Attribute],
+    name: &str,
+) -> syn::Result<(T, Option<Type>)> {
+    let mut as_type = None;
+    let mut attrs = attrs.to_vec();
+    for attr in attrs.iter_mut() {
+        let Meta::List(list) = &mut attr.meta else {
+            continue;
+        };
+        if !list.path.is_ident(name) {
+            continue;
+        }
+
+        let mut options = TokenStream2::new();
+        for option in split_options(list.tokens.clone()) {
+            match parse_as_option(&option)? {
+                Some(ty) if as_type.is_some() => {
+                    return Err(syn::Error::new_spanned(ty, "Duplicate field `as`"))
+                }
+                Some(ty) => as_type = Some(ty),
+                None => {
+                    if !options.is_empty() {
+                        options.extend(quote! { , });
+                    }
+                    options.extend(option);
+                }
+            }
+        }
+        list.tokens = options;
+    }
+    Ok((T::from_attributes(&attrs)?, as_type))
+}
+
+/// The `as` option is parsed by `parse_args` rather than by darling.
+fn no_as_type() -> Option<Type> {
+    None
+}
+
+/// Splits the options of an attribute at its top level commas. The commas
+/// between the generic arguments of an `as` type do not end the option.
+fn split_options(tokens: TokenStream2) -> Vec<Vec<TokenTree>> {
+    let mut options: Vec<Vec<TokenTree>> = vec![Vec::new()];
+    let mut depth = 0usize;
+    for token in tokens {
+        let option = options.last_mut().unwrap();
+        let is_as = matches!(option.first(), Some(TokenTree::Ident(ident)) if ident == "as");
+        if let TokenTree::Punct(punct) = &token {
+            match punct.as_char() {
+                ',' if depth == 0 => {
+                    options.push(Vec::new());
+                    continue;
+                }
+                '<' if is_as => depth += 1,
+                '>' if is_as => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        option.push(token);
+    }
+    options.retain(|option| !option.is_empty());
+    options
+}
+
+/// Parses the type of an `as = ...` option, which may be written as is,
+/// such as `as = u32`, or within a string, such as `as = "Vec<u8>"`.
+/// Returns `None` for any other option.
+fn parse_as_option(option: &[TokenTree]) -> syn::Result<Option<Type>> {
+    let [TokenTree::Ident(ident), TokenTree::Punct(eq), value @ ..] = option else {
+        return Ok(None);
+    };
+    // the field holding the type is not an option of its own
+    if ident == "as_type" {
+        return Err(syn::Error::new(
+            ident.span(),
+            "Unknown field: `as_type`. Did you mean `as`?",
+        ));
+    }
+    if ident != "as" || eq.as_char() != '=' {
+        return Ok(None);
+    }
+
+    let value: TokenStream2 = value.iter().cloned().collect();
+    let ty = match syn::parse2::<syn::LitStr>(value.clone()) {
+        Ok(text) => text.parse(),
+        Err(_) => syn::parse2(value.clone()),
+    };
+    ty.map(Some)
+        .map_err(|_| syn::Error::new(value.span(), "Expected a type, such as u32"))
+}
+
+/// What the unpacking code of a field does when the field fails to unpack.
+#[derive(Debug, Clone, Copy)]
+enum OnError {
+    /// Return the error from the enclosing function.
+    Return,
+    /// Push a `Diagnostic` onto `diagnostics`, and resume after the field
+    /// if its size is fixed or return the partial `result` otherwise.
+    Collect,
+}
+
+/// Process the given field and output the to_ascii
+/// and from_ascii tokens.
+fn process_field(
+    from_ascii_tokens: TokenStream2,
+    to_ascii_tokens: TokenStream2,
+    pack_field: &PackField,
+    target: &FieldTarget,
+    on_error: OnError,
+) -> syn::Result<(TokenStream2, TokenStream2)> {
+    let empty = TokenStream2::new();
+    let field = pack_field.field;
+    let attr = pack_field.attr.clone();
+    let when = match &attr {
+        FieldAttr::Pack(args) => args.when.clone(),
+        _ => None,
+    };
+    let (from, to) = match attr {
+        // leave the default value
+        FieldAttr::Ignore => return Ok((from_ascii_tokens, to_ascii_tokens)),
+        FieldAttr::Pack(args) => generate_pack_tokens(empty.clone(), empty, *args, field, target)?,
+        FieldAttr::PackVec(args) => {
+            generate_pack_vec_tokens(empty.clone(), empty, *args, field, target)?
+        }
+        FieldAttr::PackStatic(args) => {
+            generate_pack_static_tokens(empty.clone(), empty, args, target)?
+        }
+    };
+
+    // errors unpacking the field are given the context of the field, which
+    // starts at `field_start` within the input.
+    let lit_name = &target.name;
+    let size = match target.delimiter {
+        Some(_) => quote! { None },
+        None => size::present_size(pack_field)?,
+    };
+    let type_name = layout::type_name(&field.ty);
+    let handle_error = match on_error {
+        OnError::Return => quote! { return Err(e); },
+        OnError::Collect => quote! {
+            diagnostics.push(::ascii_pack::Diagnostic {
+                field: Some(#lit_name),
+                offset: field_start,
+                error: e,
+            });
+            let size: Option<usize> = #size;
+            match size {
+                Some(size) => left_bound = field_start + size,
+                None => return (result, diagnostics),
+            }
+        },
+    };
+    let from = quote! {
+        let field_start = left_bound;
+        if let Err(e) = (|| -> ::core::result::Result<(), ::ascii_pack::AsciiPackError> {
+            #from
+            Ok(())
+        })() {
+            let e = e.context(
+                ::ascii_pack::PathSegment::Field(#lit_name),
+                input,
+                field_start,
+                #size,
+                #type_name,
+            );
+            #handle_error
+        }
+    };
+
+    // conditional fields are only read and written when the condition on
+    // the fields before them holds, which are on `self` while packing.
+    let (from, to) = match when {
+        Some(when) => (
+            quote! {
+                if #when {
+                    #from
+                }
+            },
+            quote! {
+                if {
+                    let result = self;
+                    #when
+                } {
+                    #to
+                }
+            },
+        ),
+        None => (from, to),
+    };
+
+    let pack_first = &target.pack_first;
+    Ok((
+        quote! { #from_ascii_tokens #from },
+        quote! { #to_ascii_tokens #pack_first #to },
+    ))
+}
+
+/// Generates the `AsciiPack` and `AsciiPackUnsized` impls for a struct.
+fn derive_struct(input: &DeriveInput, data: &syn::DataStruct) -> syn::Result<TokenStream2> {
+    let struc = &input.ident;
+    let args = PackStructArgs::from_attributes(&input.attrs)?;
+    if args.transparent.is_present() {
+        if args.trailing.is_some() {
+            return Err(syn::Error::new(
+                struc.span(),
+                "trailing cannot be used with transparent structs!",
+            ));
+        }
+        return derive_transparent_struct(input, data);
+    }
+    let trailing = args.trailing.unwrap_or_default();
+    let fields = PackField::parse_all(&data.fields)?;
+
+    let mut generics = input.generics.clone();
+    add_field_bounds(&mut generics, fields.iter())?;
+    if generics.type_params().next().is_some() {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { Self: ::core::default::Default });
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut targets: Vec<FieldTarget> = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| FieldTarget::struct_field(field, index))
+        .collect();
+    FieldTarget::resolve_lengths(&fields, &mut targets)?;
+    FieldTarget::resolve_delimiters(
+        &fields,
+        &mut targets,
+        args.separator.as_ref(),
+        args.quote,
+        args.escape,
+    )?;
+
+    // the fields of separated structs vary in width
+    let separated = args.separator.is_some();
+    let struct_size = match separated {
+        true => quote! { None },
+        false => size::fields_size(0, &fields)?,
+    };
+    let checks = size::size_checks(&fields, targets.iter().map(|target| target.name.clone()))?;
+    let (check_impl, check) = size::size_check_impl(struc, &generics, checks);
+    let struct_layout = layout::fields_layout(0, &fields, separated)?;
+    let layout_impl = layout::layout_impl(struc, &generics);
 
     let mut from_ascii_tokens = quote! {
-        use std::str::FromStr;
+        #check
         let mut left_bound = 0usize;
-        let mut result = #struc::default();
+        let mut result = <Self as ::core::default::Default>::default();
     };
     let mut to_ascii_tokens = quote! {
+        #check
         let mut result = String::new();
     };
 
-    for field in data.fields.iter() {
-        let (from, to) = process_field(from_ascii_tokens, to_ascii_tokens, field)?;
+    let mut report_tokens = quote! {
+        #check
+        let mut left_bound = 0usize;
+        let mut result = <Self as ::core::default::Default>::default();
+        let mut diagnostics = Vec::new();
+    };
+
+    for (field, target) in fields.iter().zip(targets.iter()) {
+        let (from, to) = process_field(
+            from_ascii_tokens,
+            to_ascii_tokens,
+            field,
+            target,
+            OnError::Return,
+        )?;
         from_ascii_tokens = from;
         to_ascii_tokens = to;
+
+        let (report, _) = process_field(
+            report_tokens,
+            TokenStream2::new(),
+            field,
+            target,
+            OnError::Collect,
+        )?;
+        report_tokens = report;
     }
 
+    report_tokens = quote! {
+        #report_tokens
+        if let Err(e) = #trailing.check(input, left_bound) {
+            diagnostics.push(::ascii_pack::Diagnostic {
+                field: None,
+                offset: left_bound,
+                error: e,
+            });
+        }
+        (result, diagnostics)
+    };
+
     let final_from_ascii_tokens = quote! {
         #from_ascii_tokens
-        return Ok(result)
+        #trailing.check(input, left_bound)?;
+        Ok(result)
     };
 
     to_ascii_tokens = quote! {
         #to_ascii_tokens
-        return Ok(result)
+        Ok(result)
     };
 
-    let tokens = quote! {
-        impl ::ascii_pack::AsciiPack for #struc {
-            fn from_ascii(input: &str) -> Result<Self, ::ascii_pack::AsciiPackError> {
+    Ok(quote! {
+        #check_impl
+        #layout_impl
+
+        impl #impl_generics ::ascii_pack::AsciiPack for #struc #ty_generics #where_clause {
+            const SIZE: Option<usize> = #struct_size;
+            const LAYOUT: &'static [::ascii_pack::FieldLayout] = #struct_layout;
+
+            fn from_ascii(input: &str) -> ::core::result::Result<Self, ::ascii_pack::AsciiPackError> {
                 #final_from_ascii_tokens
             }
 
-            fn to_ascii(&self) -> Result<String, ::ascii_pack::AsciiPackError> {
+            fn to_ascii(&self) -> ::core::result::Result<String, ::ascii_pack::AsciiPackError> {
                 #to_ascii_tokens
             }
         }
 
-        impl ::ascii_pack::AsciiPackUnsized for #struc {
-            fn from_ascii_unsized(input: &str) -> Result<(Self, usize), ::ascii_pack::AsciiPackError> {
+        impl #impl_generics ::ascii_pack::AsciiPackUnsized for #struc #ty_generics #where_clause {
+            fn from_ascii_unsized(input: &str) -> ::core::result::Result<(Self, usize), ::ascii_pack::AsciiPackError> {
                 #from_ascii_tokens
                 Ok((result, left_bound))
             }
         }
+
+        impl #impl_generics ::ascii_pack::AsciiPackReport for #struc #ty_generics #where_clause {
+            fn from_ascii_report(input: &str) -> (Self, Vec<::ascii_pack::Diagnostic>) {
+                #report_tokens
+            }
+        }
+    })
+}
+
+/// Generates the `AsciiPack` impls for a `#[pack_struct(transparent)]`
+/// struct, which reuses the encoding of its single field directly.
+fn derive_transparent_struct(
+    input: &DeriveInput,
+    data: &syn::DataStruct,
+) -> syn::Result<TokenStream2> {
+    let struc = &input.ident;
+    let field = match data.fields.len() {
+        1 => data.fields.iter().next().unwrap(),
+        _ => {
+            return Err(syn::Error::new(
+                struc.span(),
+                "Transparent structs must have exactly one field!",
+            ))
+        }
+    };
+
+    if let Some(attr) = field.attrs.iter().find(|attr| {
+        ["pack", "pack_ignore", "pack_vec", "pack_static"]
+            .iter()
+            .any(|name| attr.path().is_ident(name))
+    }) {
+        return Err(syn::Error::new(
+            attr.span(),
+            "The field of a transparent struct cannot have an AsciiPack attribute!",
+        ));
+    }
+
+    let ty = &field.ty;
+    let member = match &field.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(0.into()),
+    };
+
+    let mut generics = input.generics.clone();
+    if uses_type_params(ty, &generics) {
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { #ty: ::ascii_pack::AsciiPack });
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let layout_impl = layout::layout_impl(struc, &generics);
+
+    // unsized and report unpacking are forwarded to the field when its type
+    // supports them, which the higher-ranked bounds leave to the compiler to
+    // check where they are used rather than failing when it does not.
+    let forwarded = |bound: TokenStream2| {
+        let mut generics = generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { for<'__ascii_pack> #ty: #bound });
+        generics
+    };
+    let unsized_generics = forwarded(quote! { ::ascii_pack::AsciiPackUnsized });
+    let unsized_where = &unsized_generics.where_clause;
+    let report_generics = forwarded(quote! { ::ascii_pack::AsciiPackReport });
+    let report_where = &report_generics.where_clause;
+
+    Ok(quote! {
+        #layout_impl
+
+        impl #impl_generics ::ascii_pack::AsciiPack for #struc #ty_generics #where_clause {
+            const SIZE: Option<usize> = <#ty as ::ascii_pack::AsciiPack>::SIZE;
+            const LAYOUT: &'static [::ascii_pack::FieldLayout] = <#ty as ::ascii_pack::AsciiPack>::LAYOUT;
+            const PAD: char = <#ty as ::ascii_pack::AsciiPack>::PAD;
+
+            fn from_ascii(input: &str) -> ::core::result::Result<Self, ::ascii_pack::AsciiPackError> {
+                Ok(Self {
+                    #member: <#ty as ::ascii_pack::AsciiPack>::from_ascii(input)?,
+                })
+            }
+
+            fn to_ascii(&self) -> ::core::result::Result<String, ::ascii_pack::AsciiPackError> {
+                ::ascii_pack::AsciiPack::to_ascii(&self.#member)
+            }
+        }
+
+        impl #impl_generics ::ascii_pack::AsciiPackUnsized for #struc #ty_generics #unsized_where {
+            fn from_ascii_unsized(input: &str) -> ::core::result::Result<(Self, usize), ::ascii_pack::AsciiPackError> {
+                let (value, consumed) = <#ty as ::ascii_pack::AsciiPackUnsized>::from_ascii_unsized(input)?;
+                Ok((Self { #member: value }, consumed))
+            }
+        }
+
+        impl #impl_generics ::ascii_pack::AsciiPackReport for #struc #ty_generics #report_where {
+            fn from_ascii_report(input: &str) -> (Self, Vec<::ascii_pack::Diagnostic>) {
+                let (value, diagnostics) = <#ty as ::ascii_pack::AsciiPackReport>::from_ascii_report(input);
+                (Self { #member: value }, diagnostics)
+            }
+        }
+    })
+}
+
+/// This macro is used to derive ascii format packing metadata and relevant functions to
+/// pack and unpack structured, sized data from strongly sized ascii formats into native
+/// rust types, bidirectionally.
+///
+/// Example:
+///
+/// ```ignore
+/// const TEST_ASCII: &str = "  EXAMPLETESTTESTTEST00120654012346543345delimeterabc";
+///
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct VecTest {
+///     #[pack(size = 9, pad_left = ' ')]
+///     pub string1: String,
+///
+///     #[pack_vec(size = 4, until = until::ascii_digit)]
+///     pub string_vec: Vec<String>,
+///
+///     #[pack_vec(size = 4, until = until::starts_with("del"))]
+///     pub usize_vec: Vec<usize>,
+///
+///     #[pack(size = 9)]
+///     pub delimeter: String,
+///
+///     #[pack_vec(size = 1, until = until::empty)]
+///     pub trailing_vec: Vec<char>,
+/// }
+/// ```
+///
+/// Sized values are right aligned and padded with `'0'` by default. This can
+/// be changed with `align = "left" | "right" | "center"` and `pad = ' '`
+/// (`pad_left = ' '` is shorthand for a right aligned `pad = ' '`), and `trim`
+/// removes the padding again when unpacking:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Name {
+///     // "TEST  " unpacks as "TEST", and packs back to "TEST  "
+///     #[pack(size = 6, align = "left", pad = ' ', trim)]
+///     pub name: String,
+/// }
+/// ```
+///
+/// Tuple structs take their attributes positionally, and a struct with a
+/// single field can reuse that field's encoding with
+/// `#[pack_struct(transparent)]`:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// #[pack_struct(transparent)]
+/// pub struct AccountNo(String);
+///
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Account(#[pack(size = 8)] AccountNo, #[pack(size = 4)] u32);
+/// ```
+///
+/// Generic structs and enums are supported, with `AsciiPack` bounds added
+/// for fields whose types depend on a type parameter. Sizes may be any
+/// constant expression, including const generic parameters:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Envelope<T> {
+///     #[pack(size = 8)]
+///     pub header: Header,
+///
+///     #[pack]
+///     pub body: T,
+/// }
+///
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Padded<const N: usize> {
+///     #[pack(size = N, pad_left = ' ')]
+///     pub value: String,
+/// }
+/// ```
+///
+/// Enums whose variants start with a record type code can be derived
+/// by tagging each variant. The variant is chosen by the leading tag
+/// when unpacking, and the tag is written back out when packing:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug)]
+/// #[pack_enum(tag_size = 2)]
+/// pub enum Record {
+///     #[pack_tag = "01"]
+///     Header {
+///         #[pack(size = 8)]
+///         date: u32,
+///     },
+///     #[pack_tag = "02"]
+///     Detail(#[pack(size = 6, pad_left = ' ')] String),
+///     #[pack_tag = "99"]
+///     Trailer,
+/// }
+/// ```
+///
+/// Unit enums can map fixed codes to variants with `#[pack(code = "...")]`.
+/// Codes are matched regardless of the space padding chosen by the parent
+/// struct, `#[pack_enum(ignore_case)]` ignores ascii case, and a single
+/// `#[pack(other)]` variant can store any unknown code:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// #[pack_enum(ignore_case)]
+/// pub enum Currency {
+///     #[default]
+///     #[pack(code = "USD")]
+///     Usd,
+///     #[pack(code = "EUR")]
+///     Eur,
+///     #[pack(other)]
+///     Other(String),
+/// }
+/// ```
+///
+/// Enums without tags can be derived with `#[pack_enum(untagged)]`. Each
+/// variant is attempted in declaration order, and if none of them unpack,
+/// the failure of every variant is reported:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug)]
+/// #[pack_enum(untagged)]
+/// pub enum Detail {
+///     Old(#[pack] OldDetail),
+///     New(#[pack] NewDetail),
+/// }
+/// ```
+///
+/// Values of variable width can be written after their length, either as
+/// `len_prefix` digits before the value or in the earlier `size_from` field:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Contact {
+///     #[pack(len_prefix = 3)]
+///     pub name: String,
+///
+///     #[pack(size = 2)]
+///     pub note_len: u8,
+///
+///     #[pack(size_from = note_len)]
+///     pub note: String,
+/// }
+/// ```
+///
+/// `pack_vec` fields can take their number of items from an earlier field
+/// with `count = field` rather than an `until` function, optionally bounded
+/// with `min` and `max`. With `rewrite`, the number of items is packed as the
+/// count field:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Order {
+///     #[pack(size = 2)]
+///     pub line_count: u8,
+///
+///     #[pack_vec(size = 6, count = line_count, max = 20, rewrite)]
+///     pub lines: Vec<u32>,
+/// }
+/// ```
+///
+/// A `count` which is not the name of a field, such as `count = 12` or
+/// `count = { N }`, fixes the number of items. Arrays can also be packed with
+/// `pack_vec`, with the number of items fixed by their length:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Year {
+///     #[pack_vec(size = 9)]
+///     pub monthly_amounts: [u64; 12],
+/// }
+/// ```
+///
+/// Fields with a `delimiter` end with it instead of having a fixed width.
+/// With `#[pack_struct(separator = ",")]`, every field is delimited by the
+/// separator apart from the last, which ends with the input. Values holding
+/// the delimiter are surrounded by the struct's `quote` character, or have
+/// it preceded by its `escape` character:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// #[pack_struct(separator = ",", quote = '"')]
+/// pub struct Row {
+///     #[pack]
+///     pub id: u32,
+///
+///     #[pack]
+///     pub name: String,
+/// }
+/// ```
+///
+/// `Option` fields are `None` when the field is blank, which is when it only
+/// holds the `blank` character, a space unless specified otherwise. `None`
+/// packs as a field filled with the `blank` character:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Record {
+///     #[pack(size = 8)]
+///     pub date: Option<u32>,
+///
+///     #[pack(size = 4, blank = '0')]
+///     pub count: Option<u16>,
+/// }
+/// ```
+///
+/// Numbers are written with their sign before the digits with `sign =
+/// "leading"`, after them with `sign = "trailing"`, or overpunched on the
+/// last digit as in COBOL zoned decimals with `sign = "overpunch"`. The
+/// padding goes between the sign and the digits, and positive numbers
+/// have a sign too with `plus`:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Amounts {
+///     // -12 packs as "-0012"
+///     #[pack(size = 5, sign = "leading")]
+///     pub leading: i32,
+///
+///     // -12 packs as "001K"
+///     #[pack(size = 4, sign = "overpunch")]
+///     pub zoned: i64,
+/// }
+/// ```
+///
+/// Fields with a `scale` have an implied decimal point that many digits from
+/// the end, converting exactly through `ascii_pack::ImpliedDecimal`, which
+/// is implemented for integers, floats and, with the `rust_decimal` feature,
+/// `rust_decimal::Decimal`:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Debug, Default)]
+/// pub struct Payment {
+///     // "000012345" is 123.45
+///     #[pack(size = 9, scale = 2)]
+///     pub amount: f64,
+/// }
+/// ```
+///
+/// Numbers can be written with a fixed `precision`, which integers reject,
+/// a `decimal` separator other than `.` and a different thousands `grouping`
+/// character, or in another base with `radix` and `case = "upper"` for
+/// upper case letter digits:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Debug, Default)]
+/// pub struct Formatted {
+///     // 3.25 packs as "003,250"
+///     #[pack(size = 7, precision = 3, decimal = ',')]
+///     pub rate: f64,
+///
+///     #[pack(size = 4, radix = 16, case = "upper")]
+///     pub code: u16,
+/// }
+/// ```
+///
+/// Dates and times are packed with a `date` pattern of `%Y`, `%y`, `%m`,
+/// `%d`, `%j`, `%H`, `%M` and `%S` fields, through `ascii_pack::PackDate`,
+/// which the `chrono` and `time` features implement. Two digit years below
+/// the `pivot`, 69 unless specified otherwise, are in the 2000s, and
+/// `Option` dates are `None` when they are all zeros or spaces, while times
+/// of day are only `None` when they are all `blank`:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Account {
+///     #[pack(size = 6, date = "%y%m%d", pivot = 50)]
+///     pub opened: NaiveDate,
+///
+///     #[pack(size = 8, date = "%Y%m%d")]
+///     pub closed: Option<NaiveDate>,
+/// }
+/// ```
+///
+/// `bool` fields are packed as one of two texts with `bool = ("Y", "N")`,
+/// which `ignore_case` matches without regard to ascii case when unpacking,
+/// and `Option<bool>` fields are `None` when blank:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Flags {
+///     #[pack(size = 1, bool = ("1", "0"))]
+///     pub active: bool,
+///
+///     #[pack(size = 1, bool = ("Y", "N"), ignore_case)]
+///     pub consent: Option<bool>,
+/// }
+/// ```
+///
+/// Fields of types without a fitting `AsciiPack` impl can be converted by
+/// the `from_ascii` and `to_ascii` functions of a `with` module, or packed
+/// `as` another type they convert from and into with `TryFrom`. Both apply
+/// to each item of `pack_vec` fields:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Debug, Default)]
+/// pub struct Shift {
+///     #[pack(size = 6, as = u32)]
+///     pub account: AccountId,
+///
+///     #[pack_vec(size = 4, count = 2, with = hhmm)]
+///     pub breaks: Vec<Duration>,
+/// }
+/// ```
+///
+/// Struct fields with `when = expr` are only present when the expression,
+/// which can refer to the earlier fields of `result`, holds. Otherwise they
+/// are skipped while unpacking, leaving their default value, and while packing:
+///
+/// ```ignore
+/// #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+/// pub struct Record {
+///     #[pack(size = 1)]
+///     pub kind: String,
+///
+///     #[pack(size = 6, when = result.kind == "R")]
+///     pub refund: u32,
+/// }
+/// ```
+///
+/// `from_ascii` rejects input remaining after the last field of a struct,
+/// unless `#[pack_struct(trailing = "ignore")]` or `trailing = "whitespace"`
+/// allows it. Tagged and untagged enums take the same option in
+/// `#[pack_enum(...)]`.
+///
+/// Structs also implement `ascii_pack::AsciiPackReport`, which reports the
+/// failure of every field of a record at once instead of the first one.
+///
+/// Every derived type also implements `ascii_pack::Layout`, describing the
+/// name, kind, size, offset and padding of each field, or of each variant
+/// for enums, along with the layouts of nested types:
+///
+/// ```ignore
+/// for field in Record::layout() {
+///     println!("{} at {:?}", field.name, field.offset);
+/// }
+/// ```
+#[manyhow(proc_macro_derive(
+    AsciiPack,
+    attributes(
+        pack,
+        pack_ignore,
+        pack_vec,
+        pack_static,
+        pack_struct,
+        pack_tag,
+        pack_enum
+    )
+))]
+pub fn derive_ascii_pack(item: proc_macro::TokenStream) -> syn::Result<proc_macro::TokenStream> {
+    let input = syn::parse::<DeriveInput>(item)?;
+    let tokens = match &input.data {
+        syn::Data::Struct(s) => derive_struct(&input, s)?,
+        syn::Data::Enum(e) => enums::derive_enum(&input, e)?,
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "AsciiPack cannot be derived for unions!",
+            ))
+        }
     };
 
     Ok(tokens.into())
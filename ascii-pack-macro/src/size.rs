@@ -0,0 +1,129 @@
+use crate::{FieldAttr, PackField};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+
+/// Tokens evaluating to the packed width of a field as an `Option<usize>`.
+/// Conditional fields have no width, since they may be absent.
+pub(crate) fn field_size(field: &PackField) -> syn::Result<TokenStream2> {
+    match &field.attr {
+        FieldAttr::Pack(args) if args.when.is_some() => Ok(quote! { None }),
+        _ => present_size(field),
+    }
+}
+
+/// Tokens evaluating to the packed width of a field when it is present.
+pub(crate) fn present_size(PackField { field, attr }: &PackField) -> syn::Result<TokenStream2> {
+    let ty = &field.ty;
+    Ok(match attr {
+        FieldAttr::Ignore => quote! { Some(0usize) },
+        FieldAttr::Pack(args) if args.is_variable() => quote! { None },
+        FieldAttr::Pack(args) => match &args.size {
+            Some(size) => quote! { Some(#size) },
+            None => {
+                let ty = args.packed_type(field);
+                quote! { <#ty as ::ascii_pack::AsciiPack>::SIZE }
+            }
+        },
+        FieldAttr::PackVec(args) => match args.fixed_count(ty) {
+            Some(count) => {
+                let item_size = match &args.size {
+                    Some(size) => quote! { Some(#size) },
+                    None => {
+                        let item = args.item_type(ty)?;
+                        quote! { <#item as ::ascii_pack::AsciiPack>::SIZE }
+                    }
+                };
+                quote! { ::ascii_pack::repeat_size(#count, #item_size) }
+            }
+            None => quote! { None },
+        },
+        FieldAttr::PackStatic(args) => {
+            let size = args.text.len();
+            quote! { Some(#size) }
+        }
+    })
+}
+
+/// Tokens evaluating to the combined packed width of `fields`, which are
+/// packed one after another following `prefix` characters.
+pub(crate) fn fields_size(prefix: usize, fields: &[PackField]) -> syn::Result<TokenStream2> {
+    let sizes = fields
+        .iter()
+        .map(field_size)
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(quote! {
+        ::ascii_pack::sum_sizes(&[Some(#prefix), #(#sizes),*])
+    })
+}
+
+/// Tokens which panic at compile time when the declared size of a field
+/// disagrees with the `SIZE` of the type packed into it.
+pub(crate) fn size_checks(
+    fields: &[PackField],
+    names: impl Iterator<Item = String>,
+) -> syn::Result<TokenStream2> {
+    let mut checks = TokenStream2::new();
+    for (PackField { field, attr }, name) in fields.iter().zip(names) {
+        let (ty, size) = match attr {
+            FieldAttr::Pack(args) if args.is_formatted() => continue,
+            FieldAttr::Pack(args) => match &args.size {
+                Some(size) => (args.packed_type(field).clone(), size.clone()),
+                None => continue,
+            },
+            FieldAttr::PackVec(args) if args.with.is_some() => continue,
+            FieldAttr::PackVec(args) => match &args.size {
+                Some(size) => (args.item_type(&field.ty)?, size.clone()),
+                None => continue,
+            },
+            _ => continue,
+        };
+
+        let message = format!(
+            "the size of field `{}` does not match the SIZE of `{}`",
+            name,
+            ty.to_token_stream()
+        );
+        checks = quote! {
+            #checks
+            if let Some(size) = <#ty as ::ascii_pack::AsciiPack>::SIZE {
+                if size != (#size) {
+                    panic!(#message);
+                }
+            }
+        };
+    }
+    Ok(checks)
+}
+
+/// Generates an inherent impl holding the compile-time size `checks`, along
+/// with a statement referencing them. The statement is placed in the packing
+/// functions so that the checks are evaluated for every instantiation of a
+/// generic type, while non-generic types are checked unconditionally.
+pub(crate) fn size_check_impl(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    checks: TokenStream2,
+) -> (TokenStream2, TokenStream2) {
+    if checks.is_empty() {
+        return (TokenStream2::new(), TokenStream2::new());
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let eager_check = match generics.params.is_empty() {
+        true => quote! { const _: () = #ident::__ASCII_PACK_SIZE_CHECK; },
+        false => TokenStream2::new(),
+    };
+
+    let check_impl = quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc(hidden)]
+            const __ASCII_PACK_SIZE_CHECK: () = { #checks };
+        }
+
+        #eager_check
+    };
+    let reference = quote! {
+        let () = Self::__ASCII_PACK_SIZE_CHECK;
+    };
+    (check_impl, reference)
+}
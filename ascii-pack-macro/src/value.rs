@@ -0,0 +1,523 @@
+use darling::FromMeta;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::{Expr, ExprLit, Field, GenericArgument, Lit, Path, PathArguments, Type};
+
+use crate::{layout, PackArgs, Padding};
+
+/// Where the sign of a number is written, mirroring `ascii_pack::Sign`.
+#[derive(Debug, Clone, Copy, FromMeta)]
+#[darling(rename_all = "snake_case")]
+pub(crate) enum Sign {
+    Leading,
+    Trailing,
+    Overpunch,
+}
+
+impl ToTokens for Sign {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.extend(match self {
+            Sign::Leading => quote! { ::ascii_pack::Sign::Leading },
+            Sign::Trailing => quote! { ::ascii_pack::Sign::Trailing },
+            Sign::Overpunch => quote! { ::ascii_pack::Sign::Overpunch },
+        });
+    }
+}
+
+/// Case of the letter digits of numbers in a radix above 10.
+#[derive(Debug, Default, Clone, Copy, FromMeta)]
+#[darling(rename_all = "snake_case")]
+pub(crate) enum Case {
+    #[default]
+    Lower,
+    Upper,
+}
+
+/// The texts of `true` and `false`, written as `bool = ("Y", "N")`.
+#[derive(Debug, Clone)]
+pub(crate) struct BoolTexts(String, String);
+
+impl FromMeta for BoolTexts {
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        let usage = || {
+            darling::Error::custom("Expected the texts of true and false, such as (\"Y\", \"N\")")
+                .with_span(expr)
+        };
+        let Expr::Tuple(tuple) = expr else {
+            return Err(usage());
+        };
+        let texts = tuple
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(text),
+                    ..
+                }) => Ok(text.value()),
+                _ => Err(usage()),
+            })
+            .collect::<darling::Result<Vec<_>>>()?;
+
+        match <[String; 2]>::try_from(texts) {
+            Ok([true_text, false_text]) => Ok(BoolTexts(true_text, false_text)),
+            Err(_) => Err(usage()),
+        }
+    }
+}
+
+/// How a value is written, before it is signed and padded.
+enum Format {
+    /// As written by `to_ascii`.
+    Plain,
+    /// With an implied decimal point this many digits from the end.
+    Scaled(u32),
+    /// In the given base, with upper case letter digits when set.
+    Radix(u32, bool),
+    /// With a fixed number of fractional digits, or other separators.
+    Decimal {
+        precision: Option<usize>,
+        decimal: char,
+        grouping: Option<char>,
+    },
+    /// As a date or time with the given pattern and two digit year pivot.
+    Date { pattern: String, pivot: u32 },
+    /// As one of the texts of `true` and `false`.
+    Bool { texts: BoolTexts, ignore_case: bool },
+    /// By the `from_ascii` and `to_ascii` functions of a module.
+    With(Path),
+}
+
+impl Format {
+    /// Resolves the format of a pack field, which is one of `scale`,
+    /// `radix`, `date`, `bool`, `with`, or the decimal formatting options.
+    fn new(args: &PackArgs, field: &Field) -> syn::Result<Format> {
+        let error = |message: String| Err(syn::Error::new(field.span(), message));
+        if args.case.is_some() && args.radix.is_none() {
+            return error("case can only be used together with radix!".to_owned());
+        }
+        if args.pivot.is_some() && args.date.is_none() {
+            return error("pivot can only be used together with date!".to_owned());
+        }
+        if args.ignore_case.is_present() && args.bool.is_none() {
+            return error("ignore_case can only be used together with bool!".to_owned());
+        }
+
+        let decimal = args.precision.is_some() || args.decimal.is_some() || args.grouping.is_some();
+        let formats = [
+            args.scale.is_some(),
+            args.radix.is_some(),
+            args.date.is_some(),
+            args.bool.is_some(),
+            args.with.is_some(),
+            decimal,
+        ];
+        if formats.iter().filter(|set| **set).count() > 1 {
+            return error(
+                "scale, radix, date, bool, with, and precision, decimal or grouping cannot be combined!"
+                    .to_owned(),
+            );
+        }
+
+        if let Some(scale) = args.scale {
+            return Ok(Format::Scaled(scale));
+        }
+        if let Some(radix) = args.radix {
+            if !(2..=36).contains(&radix) {
+                return error("radix must be between 2 and 36!".to_owned());
+            }
+            let upper = matches!(args.case.unwrap_or_default(), Case::Upper);
+            return Ok(Format::Radix(radix, upper));
+        }
+        if let Some(pattern) = &args.date {
+            if let Err(message) = check_date_pattern(pattern) {
+                return error(message);
+            }
+            return match args.pivot.unwrap_or(69) {
+                pivot @ 0..=99 => Ok(Format::Date {
+                    pattern: pattern.clone(),
+                    pivot,
+                }),
+                _ => error("pivot must be a two digit year!".to_owned()),
+            };
+        }
+        if let Some(texts) = &args.bool {
+            let ignore_case = args.ignore_case.is_present();
+            let BoolTexts(true_text, false_text) = texts;
+            let same = match ignore_case {
+                true => true_text.eq_ignore_ascii_case(false_text),
+                false => true_text == false_text,
+            };
+            if same {
+                return error("The texts of true and false must differ!".to_owned());
+            }
+            return Ok(Format::Bool {
+                texts: texts.clone(),
+                ignore_case,
+            });
+        }
+        if let Some(with) = &args.with {
+            return Ok(Format::With(with.clone()));
+        }
+        if decimal {
+            let ty = args.packed_type(field);
+            if args.precision.is_some() && is_integer(ty) {
+                return error(format!(
+                    "precision cannot be used on the integer type {}!",
+                    layout::type_name(ty)
+                ));
+            }
+            let decimal = args.decimal.unwrap_or('.');
+            if args.grouping == Some(decimal) {
+                return error("grouping and decimal must be different characters!".to_owned());
+            }
+            return Ok(Format::Decimal {
+                precision: args.precision,
+                decimal,
+                grouping: args.grouping,
+            });
+        }
+        Ok(Format::Plain)
+    }
+
+    /// Tokens evaluating to the value of type `ty` read from `text`.
+    fn unpack(&self, ty: &Type, text: TokenStream2) -> TokenStream2 {
+        match self {
+            Format::Plain => quote! { <#ty as ::ascii_pack::AsciiPack>::from_ascii(#text)? },
+            Format::Scaled(scale) => {
+                quote! { <#ty as ::ascii_pack::ImpliedDecimal>::from_implied(#text, #scale)? }
+            }
+            Format::Radix(radix, _) => {
+                quote! { <#ty as ::ascii_pack::Radix>::from_radix(#text, #radix)? }
+            }
+            Format::Decimal {
+                decimal, grouping, ..
+            } => {
+                let separators = separators(*decimal, *grouping);
+                quote! { <#ty as ::ascii_pack::AsciiPack>::from_ascii(&#separators.read(#text))? }
+            }
+            Format::Date { pattern, pivot } => {
+                quote! { (::ascii_pack::DateFormat { pattern: #pattern, pivot: #pivot }).read::<#ty>(#text)? }
+            }
+            Format::Bool { texts, ignore_case } => {
+                let format = bool_format(texts, *ignore_case);
+                quote! { #format.read(#text)? }
+            }
+            Format::With(with) => quote! { #with::from_ascii(#text)? },
+        }
+    }
+
+    /// Tokens evaluating to the text of the value referenced by `value`.
+    fn pack(&self, value: TokenStream2) -> TokenStream2 {
+        match self {
+            Format::Plain => quote! { ::ascii_pack::AsciiPack::to_ascii(#value)? },
+            Format::Scaled(scale) => {
+                quote! { ::ascii_pack::ImpliedDecimal::to_implied(#value, #scale)? }
+            }
+            Format::Radix(radix, upper) => {
+                quote! { ::ascii_pack::Radix::to_radix(#value, #radix, #upper) }
+            }
+            Format::Decimal {
+                precision,
+                decimal,
+                grouping,
+            } => {
+                let separators = separators(*decimal, *grouping);
+                let text = match precision {
+                    Some(precision) => quote! { format!("{:.*}", #precision, #value) },
+                    None => quote! { ::ascii_pack::AsciiPack::to_ascii(#value)? },
+                };
+                quote! { #separators.write(&#text) }
+            }
+            Format::Date { pattern, pivot } => {
+                quote! { (::ascii_pack::DateFormat { pattern: #pattern, pivot: #pivot }).write(#value)? }
+            }
+            Format::Bool { texts, ignore_case } => {
+                let format = bool_format(texts, *ignore_case);
+                quote! { #format.write(#value) }
+            }
+            Format::With(with) => quote! { #with::to_ascii(#value)? },
+        }
+    }
+}
+
+/// Checks that a date pattern only holds the specifiers of
+/// `ascii_pack::DateFormat`.
+fn check_date_pattern(pattern: &str) -> Result<(), String> {
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        match chars.next() {
+            Some('%' | 'Y' | 'y' | 'm' | 'd' | 'j' | 'H' | 'M' | 'S') => {}
+            Some(spec) => return Err(format!("Unknown date specifier '%{}'!", spec)),
+            None => return Err("The date pattern ends with an incomplete specifier!".to_owned()),
+        }
+    }
+    Ok(())
+}
+
+/// Whether a checked date pattern holds a year, month or day, rather than
+/// only a time of day.
+fn has_date_part(pattern: &str) -> bool {
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' && matches!(chars.next(), Some('Y' | 'y' | 'm' | 'd' | 'j')) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Tokens of the `ascii_pack::BoolFormat` of a boolean.
+fn bool_format(BoolTexts(true_text, false_text): &BoolTexts, ignore_case: bool) -> TokenStream2 {
+    quote! {
+        (::ascii_pack::BoolFormat {
+            true_text: #true_text,
+            false_text: #false_text,
+            ignore_case: #ignore_case,
+        })
+    }
+}
+
+/// Tokens of the `ascii_pack::Separators` of a decimal number.
+fn separators(decimal: char, grouping: Option<char>) -> TokenStream2 {
+    let grouping = match grouping {
+        Some(grouping) => quote! { Some(#grouping) },
+        None => quote! { None },
+    };
+    quote! {
+        ::ascii_pack::Separators {
+            decimal: #decimal,
+            grouping: #grouping,
+        }
+    }
+}
+
+/// How the value of a pack field is converted to and from the text of the
+/// field, including its format, padding and sign.
+pub(crate) struct Value {
+    /// The type packed into the field, which is the `as` type when given
+    /// and `T` for `Option<T>` fields.
+    ty: Type,
+    /// The type of the value converted from and into the `as` type.
+    converted: Option<Type>,
+    /// The character filling `Option` fields which are `None`.
+    blank: Option<char>,
+    /// Where the sign is written, and whether positive numbers have one.
+    sign: Option<(Sign, bool)>,
+    format: Format,
+    padding: Padding,
+}
+
+impl Value {
+    /// Resolves the value of a pack field. `Option` fields are blank
+    /// when they are `None`, filled with spaces unless specified otherwise.
+    pub(crate) fn new(args: &PackArgs, field: &Field) -> syn::Result<Value> {
+        let (ty, blank) = match option_type(&field.ty) {
+            Some(ty) => (ty.clone(), Some(args.blank.unwrap_or(' '))),
+            None if args.blank.is_some() => {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "blank can only be used on Option fields!",
+                ))
+            }
+            None => (field.ty.clone(), None),
+        };
+        let (ty, converted) = match &args.as_type {
+            Some(as_type) => (as_type.clone(), Some(ty)),
+            None => (ty, None),
+        };
+
+        let sign = match args.sign {
+            Some(sign) => Some((sign, args.plus.is_present())),
+            None if args.plus.is_present() => {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "plus can only be used together with sign!",
+                ))
+            }
+            None => None,
+        };
+
+        let padding = Padding::new(
+            args.pad_left,
+            args.align,
+            args.pad,
+            &args.trim,
+            field,
+            (!args.is_formatted()).then_some(&ty),
+        )?;
+        Ok(Value {
+            ty,
+            converted,
+            blank,
+            sign,
+            format: Format::new(args, field)?,
+            padding,
+        })
+    }
+
+    pub(crate) fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Whether the text of the value must be converted, so that it
+    /// cannot be unpacked with only the `SIZE` of its type.
+    pub(crate) fn is_encoded(&self) -> bool {
+        self.blank.is_some() || self.sign.is_some() || !matches!(self.format, Format::Plain)
+    }
+
+    /// Tokens converting the `value` unpacked as the `as` type into the
+    /// type of the field.
+    pub(crate) fn converted(&self, value: TokenStream2) -> TokenStream2 {
+        let ty = &self.ty;
+        match &self.converted {
+            Some(converted) => quote! { ::ascii_pack::convert_from::<#ty, #converted>(#value)? },
+            None => value,
+        }
+    }
+
+    /// Tokens referencing the value referenced by `pack`, converted into
+    /// the `as` type.
+    pub(crate) fn packed(&self, pack: &TokenStream2) -> TokenStream2 {
+        let ty = &self.ty;
+        match &self.converted {
+            Some(converted) => quote! { &::ascii_pack::convert_into::<#converted, #ty>(#pack)? },
+            None => pack.clone(),
+        }
+    }
+
+    /// Tokens evaluating to the value unpacked from `text`.
+    pub(crate) fn unpack(&self, text: TokenStream2) -> TokenStream2 {
+        let ty = &self.ty;
+        let Padding { align, pad, trim } = &self.padding;
+        let trimmed = match self.sign {
+            Some((sign, _)) => quote! { &#sign.unpack(#text, #trim, #align, #pad)? },
+            None => self.padding.trimmed(text.clone()),
+        };
+        let value = self.converted(self.format.unpack(ty, trimmed));
+
+        let blanks = self.blanks();
+        match self.blank {
+            Some(_) => quote! {
+                match #(::ascii_pack::is_blank(#text, #blanks))||* {
+                    true => None,
+                    false => Some(#value),
+                }
+            },
+            None => value,
+        }
+    }
+
+    /// The characters which, filling the text of an `Option` field, unpack
+    /// as `None`.
+    fn blanks(&self) -> Vec<char> {
+        let mut blanks: Vec<char> = self.blank.into_iter().collect();
+        // dates are never all zeros or spaces, so both are blank dates,
+        // while a time alone is all zeros at midnight
+        if let (Some(_), Format::Date { pattern, .. }) = (self.blank, &self.format) {
+            if has_date_part(pattern) {
+                for blank in ['0', ' '] {
+                    if !blanks.contains(&blank) {
+                        blanks.push(blank);
+                    }
+                }
+            }
+        }
+        blanks
+    }
+
+    /// Tokens evaluating to the text of the value referenced by `pack`,
+    /// padded to `size` characters when given. `None` is packed as `size`
+    /// blank characters, or as nothing for fields without a size, and a
+    /// `Some` value packing to blank text is an error, since it would be
+    /// unpacked as `None`.
+    pub(crate) fn pack(
+        &self,
+        pack: &TokenStream2,
+        size: Option<&TokenStream2>,
+        name: &str,
+    ) -> TokenStream2 {
+        let Padding { align, pad, .. } = &self.padding;
+        let encoded = match (self.sign, size) {
+            (Some((sign, plus)), size) => {
+                let size = match size {
+                    Some(size) => quote! { Some(#size) },
+                    None => quote! { None },
+                };
+                quote! { #sign.pack(&text, #plus, #size, #align, #pad)? }
+            }
+            (None, Some(size)) => self.padding.padded(quote! { &text }, size, name),
+            (None, None) => quote! { text },
+        };
+        let text = |value: TokenStream2| {
+            let text = self.format.pack(self.packed(&value));
+            quote! {{
+                let text = #text;
+                #encoded
+            }}
+        };
+
+        match self.blank {
+            Some(blank) => {
+                let some = text(quote! { value });
+                let size = size.cloned().unwrap_or_else(|| quote! { 0 });
+                let blanks = self.blanks();
+                quote! {
+                    match #pack {
+                        Some(value) => {
+                            let text = #some;
+                            if #(::ascii_pack::is_blank(&text, #blanks))||* {
+                                return Err(::ascii_pack::AsciiPackError::Pack(format!(
+                                    "{} packs as '{}', which is blank and unpacks as None", #name, text)));
+                            }
+                            text
+                        }
+                        None => ::ascii_pack::blank(#size, #blank),
+                    }
+                }
+            }
+            None => text(pack.clone()),
+        }
+    }
+}
+
+/// Whether `ty` is a primitive integer, which ignores the precision of
+/// its formatting.
+fn is_integer(ty: &Type) -> bool {
+    const INTEGERS: [&str; 12] = [
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => type_path
+            .path
+            .get_ident()
+            .is_some_and(|ident| INTEGERS.iter().any(|integer| ident == integer)),
+        _ => false,
+    }
+}
+
+/// The `T` of a type written literally as `Option<T>`.
+fn option_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if type_path.qself.is_some() || segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The type packed into a pack field, which is `T` for `Option<T>` fields.
+pub(crate) fn packed_type(field: &Field) -> &Type {
+    option_type(&field.ty).unwrap_or(&field.ty)
+}
@@ -0,0 +1,118 @@
+//! Unpacks arbitrary input with the formats exercised by the integration
+//! tests, which must return an error rather than panic on malformed input.
+//!
+//! Run with `cargo fuzz run from_ascii` from the `ascii-pack` directory.
+
+#![no_main]
+
+use ascii_pack::{until, AsciiPack, AsciiPackUnsized, Static};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Inner {
+    #[pack(size = 5, pad_left = ' ')]
+    pub name: String,
+
+    #[pack(size = 4)]
+    pub number: usize,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Record {
+    #[pack(size = 4)]
+    pub padded_number: u32,
+
+    #[pack_ignore]
+    pub ignored: Option<usize>,
+
+    #[pack_static(text = "\r\n")]
+    pub line_ending: Static,
+
+    #[pack]
+    pub inner: Inner,
+
+    #[pack(size = 6, align = "left", pad = ' ', trim)]
+    pub trimmed: String,
+
+    #[pack_vec(size = 4, until = until::ascii_alpha)]
+    pub numbers: Vec<u32>,
+
+    #[pack_vec(size = 3, until = until::empty)]
+    pub rest: Vec<String>,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Item {
+    #[pack(size = 4)]
+    pub id: usize,
+
+    #[pack(size = 2)]
+    pub delimeter: String,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Day {
+    #[pack(size = 2)]
+    pub day_num: usize,
+
+    #[pack_vec(until = until::starts_with("\n"))]
+    pub items: Vec<Item>,
+
+    #[pack(size = 1)]
+    pub newline: char,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Days {
+    #[pack_static(text = "RECORD:\n")]
+    pub record: Static,
+
+    #[pack_vec(until = until::starts_with("R"))]
+    pub days: Vec<Day>,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+pub enum Tagged {
+    #[pack_tag = "01"]
+    Header(#[pack(size = 8)] u32),
+    #[pack_tag = "02"]
+    Detail {
+        #[pack]
+        inner: Inner,
+    },
+    #[pack_tag = "99"]
+    Trailer,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+#[pack_enum(ignore_case)]
+pub enum Code {
+    #[default]
+    #[pack(code = "USD")]
+    Usd,
+    #[pack(other)]
+    Other(String),
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+#[pack_enum(untagged)]
+pub enum Untagged {
+    Days(#[pack] Days),
+    Record(#[pack] Record),
+}
+
+/// Unpacks `input` as `T` both as a complete record and as a prefix.
+fn unpack<T: AsciiPack + AsciiPackUnsized>(input: &str) {
+    let _ = T::from_ascii(input);
+    let _ = T::from_ascii_unsized(input);
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        unpack::<Record>(input);
+        unpack::<Days>(input);
+        unpack::<Tagged>(input);
+        unpack::<Code>(input);
+        unpack::<Untagged>(input);
+    }
+});
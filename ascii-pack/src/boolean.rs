@@ -0,0 +1,40 @@
+use crate::{AsciiPackError, Result};
+
+/// The texts of `true` and `false`, selected with the `bool`
+/// option of `pack` fields, such as `bool = ("Y", "N")`.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct BoolFormat {
+    pub true_text: &'static str,
+    pub false_text: &'static str,
+    /// Match the texts without regard to ascii case when unpacking.
+    pub ignore_case: bool,
+}
+
+impl BoolFormat {
+    /// Writes the text of `value`.
+    pub fn write(&self, value: &bool) -> String {
+        match value {
+            true => self.true_text.to_owned(),
+            false => self.false_text.to_owned(),
+        }
+    }
+
+    /// Reads a value from `text`, which must be one of the two texts.
+    pub fn read(&self, text: &str) -> Result<bool> {
+        let matches = |expected: &str| match self.ignore_case {
+            true => text.eq_ignore_ascii_case(expected),
+            false => text == expected,
+        };
+
+        if matches(self.true_text) {
+            Ok(true)
+        } else if matches(self.false_text) {
+            Ok(false)
+        } else {
+            Err(AsciiPackError::Unpack(format!(
+                "'{}' is neither '{}' nor '{}'",
+                text, self.true_text, self.false_text
+            )))
+        }
+    }
+}
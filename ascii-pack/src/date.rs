@@ -0,0 +1,331 @@
+use crate::{AsciiPackError, Result};
+
+/// The parts of a date or time written by a [`DateFormat`] pattern. Parts
+/// which the pattern does not hold are `None` when unpacking.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default)]
+pub struct DateParts {
+    pub year: Option<i32>,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+    /// Day of the year, starting from 1, for Julian dates.
+    pub ordinal: Option<u32>,
+    pub hour: Option<u32>,
+    pub minute: Option<u32>,
+    pub second: Option<u32>,
+}
+
+impl DateParts {
+    /// The error for parts which are missing or do not form a valid `kind`,
+    /// such as a date or a time.
+    pub fn invalid(&self, kind: &str) -> AsciiPackError {
+        AsciiPackError::Unpack(format!("{:?} is not a valid {}", self, kind))
+    }
+}
+
+/// Dates and times which can be packed with a pattern, selected with the
+/// `date` option of `pack` fields. This is implemented for the date and
+/// time types of `chrono` and `time` when their features are enabled.
+pub trait PackDate: Sized {
+    /// Every part of the date or time.
+    fn to_parts(&self) -> DateParts;
+
+    /// Builds a date or time from the parts held by a pattern, failing
+    /// when a required part is missing or the parts are not valid.
+    fn from_parts(parts: DateParts) -> Result<Self>;
+}
+
+/// A pattern of fixed width date and time fields, such as `%Y%m%d`:
+/// * `%Y` is the 4 digit year, and `%y` the last 2 digits of the year
+/// * `%m` is the month and `%d` the day of the month, in 2 digits
+/// * `%j` is the day of the year in 3 digits, for Julian dates such as `%y%j`
+/// * `%H`, `%M` and `%S` are the hour, minute and second, in 2 digits
+/// * `%%` is a literal `%`, and any other character is written as it is
+///
+/// Two digit years fall in the century starting at `1900 + pivot`, so with
+/// a pivot of 50, `49` is 2049 and `50` is 1950.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct DateFormat {
+    pub pattern: &'static str,
+    pub pivot: u32,
+}
+
+/// A part of a date pattern.
+enum Item {
+    Literal(char),
+    Part(char, usize),
+}
+
+/// Splits a date pattern into its parts, failing on unknown specifiers.
+fn pattern_items(pattern: &str) -> std::result::Result<Vec<Item>, String> {
+    let mut items = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            items.push(Item::Literal(c));
+            continue;
+        }
+        items.push(match chars.next() {
+            Some('%') => Item::Literal('%'),
+            Some('Y') => Item::Part('Y', 4),
+            Some('j') => Item::Part('j', 3),
+            Some(spec @ ('y' | 'm' | 'd' | 'H' | 'M' | 'S')) => Item::Part(spec, 2),
+            Some(spec) => {
+                return Err(format!(
+                    "unknown date specifier '%{}' in '{}'",
+                    spec, pattern
+                ))
+            }
+            None => return Err(format!("'{}' ends with an incomplete specifier", pattern)),
+        });
+    }
+    Ok(items)
+}
+
+impl DateFormat {
+    /// Writes `value` with the pattern.
+    pub fn write<T: PackDate>(&self, value: &T) -> Result<String> {
+        let parts = value.to_parts();
+        let items = pattern_items(self.pattern).map_err(AsciiPackError::Pack)?;
+        let missing = |spec: char| {
+            AsciiPackError::Pack(format!(
+                "the value has no part for '%{}' in '{}'",
+                spec, self.pattern
+            ))
+        };
+
+        let mut result = String::new();
+        for item in items {
+            let (spec, width) = match item {
+                Item::Literal(c) => {
+                    result.push(c);
+                    continue;
+                }
+                Item::Part(spec, width) => (spec, width),
+            };
+            let value = match spec {
+                'Y' => parts.year.ok_or_else(|| missing(spec))?,
+                'y' => {
+                    let year = parts.year.ok_or_else(|| missing(spec))?;
+                    let first = 1900 + self.pivot as i32;
+                    if !(first..first + 100).contains(&year) {
+                        return Err(AsciiPackError::Pack(format!(
+                            "{} is outside the two digit years {} to {}",
+                            year,
+                            first,
+                            first + 99
+                        )));
+                    }
+                    year % 100
+                }
+                spec => {
+                    let part = match spec {
+                        'm' => parts.month,
+                        'd' => parts.day,
+                        'j' => parts.ordinal,
+                        'H' => parts.hour,
+                        'M' => parts.minute,
+                        _ => parts.second,
+                    };
+                    part.ok_or_else(|| missing(spec))? as i32
+                }
+            };
+
+            let digits = format!("{:0width$}", value, width = width);
+            if value < 0 || digits.len() > width {
+                return Err(AsciiPackError::Pack(format!(
+                    "{} does not fit in the {} digits of '%{}'",
+                    value, width, spec
+                )));
+            }
+            result.push_str(&digits);
+        }
+        Ok(result)
+    }
+
+    /// Reads a value written with the pattern from `text`.
+    pub fn read<T: PackDate>(&self, text: &str) -> Result<T> {
+        let items = pattern_items(self.pattern).map_err(AsciiPackError::Unpack)?;
+        let mismatch = || {
+            AsciiPackError::Unpack(format!(
+                "'{}' does not match the date pattern '{}'",
+                text, self.pattern
+            ))
+        };
+
+        let mut parts = DateParts::default();
+        let mut rest = text;
+        for item in items {
+            let (spec, width) = match item {
+                Item::Literal(c) => {
+                    rest = rest.strip_prefix(c).ok_or_else(mismatch)?;
+                    continue;
+                }
+                Item::Part(spec, width) => (spec, width),
+            };
+            let digits = rest.get(..width).ok_or_else(mismatch)?;
+            if !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(mismatch());
+            }
+            rest = &rest[width..];
+
+            let value: u32 = digits.parse()?;
+            match spec {
+                'Y' => parts.year = Some(value as i32),
+                'y' => {
+                    let century = if value < self.pivot { 2000 } else { 1900 };
+                    parts.year = Some(century + value as i32);
+                }
+                'm' => parts.month = Some(value),
+                'd' => parts.day = Some(value),
+                'j' => parts.ordinal = Some(value),
+                'H' => parts.hour = Some(value),
+                'M' => parts.minute = Some(value),
+                _ => parts.second = Some(value),
+            }
+        }
+
+        if !rest.is_empty() {
+            return Err(mismatch());
+        }
+        T::from_parts(parts)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+    use super::{DateParts, PackDate};
+    use crate::Result;
+
+    impl PackDate for NaiveDate {
+        fn to_parts(&self) -> DateParts {
+            DateParts {
+                year: Some(self.year()),
+                month: Some(self.month()),
+                day: Some(self.day()),
+                ordinal: Some(self.ordinal()),
+                ..Default::default()
+            }
+        }
+
+        fn from_parts(parts: DateParts) -> Result<Self> {
+            let date = match (parts.year, parts.month, parts.day, parts.ordinal) {
+                (Some(year), _, _, Some(ordinal)) => NaiveDate::from_yo_opt(year, ordinal),
+                (Some(year), Some(month), Some(day), None) => {
+                    NaiveDate::from_ymd_opt(year, month, day)
+                }
+                _ => None,
+            };
+            date.ok_or_else(|| parts.invalid("date"))
+        }
+    }
+
+    impl PackDate for NaiveTime {
+        fn to_parts(&self) -> DateParts {
+            DateParts {
+                hour: Some(self.hour()),
+                minute: Some(self.minute()),
+                second: Some(self.second()),
+                ..Default::default()
+            }
+        }
+
+        fn from_parts(parts: DateParts) -> Result<Self> {
+            NaiveTime::from_hms_opt(
+                parts.hour.unwrap_or(0),
+                parts.minute.unwrap_or(0),
+                parts.second.unwrap_or(0),
+            )
+            .ok_or_else(|| parts.invalid("time"))
+        }
+    }
+
+    impl PackDate for NaiveDateTime {
+        fn to_parts(&self) -> DateParts {
+            DateParts {
+                hour: Some(self.hour()),
+                minute: Some(self.minute()),
+                second: Some(self.second()),
+                ..self.date().to_parts()
+            }
+        }
+
+        fn from_parts(parts: DateParts) -> Result<Self> {
+            Ok(NaiveDate::from_parts(parts)?.and_time(NaiveTime::from_parts(parts)?))
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::{Date, Month, PrimitiveDateTime, Time};
+
+    use super::{DateParts, PackDate};
+    use crate::Result;
+
+    impl PackDate for Date {
+        fn to_parts(&self) -> DateParts {
+            DateParts {
+                year: Some(self.year()),
+                month: Some(self.month() as u32),
+                day: Some(self.day() as u32),
+                ordinal: Some(self.ordinal() as u32),
+                ..Default::default()
+            }
+        }
+
+        fn from_parts(parts: DateParts) -> Result<Self> {
+            let date = match (parts.year, parts.month, parts.day, parts.ordinal) {
+                (Some(year), _, _, Some(ordinal)) => u16::try_from(ordinal)
+                    .ok()
+                    .and_then(|ordinal| Date::from_ordinal_date(year, ordinal).ok()),
+                (Some(year), Some(month), Some(day), None) => u8::try_from(month)
+                    .ok()
+                    .and_then(|month| Month::try_from(month).ok())
+                    .zip(u8::try_from(day).ok())
+                    .and_then(|(month, day)| Date::from_calendar_date(year, month, day).ok()),
+                _ => None,
+            };
+            date.ok_or_else(|| parts.invalid("date"))
+        }
+    }
+
+    impl PackDate for Time {
+        fn to_parts(&self) -> DateParts {
+            DateParts {
+                hour: Some(self.hour() as u32),
+                minute: Some(self.minute() as u32),
+                second: Some(self.second() as u32),
+                ..Default::default()
+            }
+        }
+
+        fn from_parts(parts: DateParts) -> Result<Self> {
+            let part = |part: Option<u32>| u8::try_from(part.unwrap_or(0)).ok();
+            part(parts.hour)
+                .zip(part(parts.minute))
+                .zip(part(parts.second))
+                .and_then(|((hour, minute), second)| Time::from_hms(hour, minute, second).ok())
+                .ok_or_else(|| parts.invalid("time"))
+        }
+    }
+
+    impl PackDate for PrimitiveDateTime {
+        fn to_parts(&self) -> DateParts {
+            DateParts {
+                hour: Some(self.hour() as u32),
+                minute: Some(self.minute() as u32),
+                second: Some(self.second() as u32),
+                ..self.date().to_parts()
+            }
+        }
+
+        fn from_parts(parts: DateParts) -> Result<Self> {
+            Ok(PrimitiveDateTime::new(
+                Date::from_parts(parts)?,
+                Time::from_parts(parts)?,
+            ))
+        }
+    }
+}
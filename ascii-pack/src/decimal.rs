@@ -0,0 +1,117 @@
+use crate::{AsciiPackError, Result};
+
+/// Numbers packed with an implied decimal point, as in the COBOL picture
+/// `9(7)V99`, selected with the `scale` option of `pack` fields. The packed
+/// digits are the number multiplied by `10^scale`, so with a scale of 2,
+/// `000012345` holds `123.45`.
+pub trait ImpliedDecimal: Sized {
+    /// Writes the digits of the value multiplied by `10^scale`, failing when
+    /// the value has more fractional digits than the scale allows.
+    fn to_implied(&self, scale: u32) -> Result<String>;
+
+    /// Reads a value from digits holding it multiplied by `10^scale`.
+    fn from_implied(text: &str, scale: u32) -> Result<Self>;
+}
+
+/// Moves the decimal point of the number `text` right by `scale` digits,
+/// so that `123.45` becomes `12345` with a scale of 2.
+fn shift_out(text: &str, scale: u32) -> Result<String> {
+    let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let fraction = fraction.trim_end_matches('0');
+    let scale = scale as usize;
+    if fraction.len() > scale {
+        return Err(AsciiPackError::Pack(format!(
+            "{} has more than {} fractional digits",
+            text, scale
+        )));
+    }
+    Ok(format!("{}{:0<scale$}", whole, fraction))
+}
+
+/// Places the decimal point of the digits in `text` `scale` digits from the
+/// end, returning the whole and fractional digits along with the sign.
+fn shift_in(text: &str, scale: u32) -> Result<(bool, String, String)> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(AsciiPackError::Unpack(format!(
+            "'{}' is not a number with an implied decimal point",
+            text
+        )));
+    }
+
+    let scale = scale as usize;
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - scale);
+    Ok((negative, whole.to_owned(), fraction.to_owned()))
+}
+
+/// Writes the number with the sign, whole and fractional digits
+/// returned by `shift_in`.
+fn join((negative, whole, fraction): (bool, String, String)) -> String {
+    let sign = if negative { "-" } else { "" };
+    match fraction.is_empty() {
+        true => format!("{}{}", sign, whole),
+        false => format!("{}{}.{}", sign, whole, fraction),
+    }
+}
+
+macro_rules! implied_integer {
+    ($($ty:ty),*) => {$(
+        impl ImpliedDecimal for $ty {
+            fn to_implied(&self, scale: u32) -> Result<String> {
+                shift_out(&self.to_string(), scale)
+            }
+
+            fn from_implied(text: &str, scale: u32) -> Result<Self> {
+                let (negative, whole, fraction) = shift_in(text, scale)?;
+                if fraction.bytes().any(|b| b != b'0') {
+                    return Err(AsciiPackError::Unpack(format!(
+                        "'{}' has fractional digits, which {} cannot hold",
+                        text,
+                        stringify!($ty)
+                    )));
+                }
+                let sign = if negative { "-" } else { "" };
+                Ok(format!("{}{}", sign, whole).parse()?)
+            }
+        }
+    )*};
+}
+
+implied_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! implied_float {
+    ($($ty:ty),*) => {$(
+        impl ImpliedDecimal for $ty {
+            fn to_implied(&self, scale: u32) -> Result<String> {
+                if !self.is_finite() {
+                    return Err(AsciiPackError::Pack(format!(
+                        "{} cannot be packed with an implied decimal point",
+                        self
+                    )));
+                }
+                shift_out(&self.to_string(), scale)
+            }
+
+            fn from_implied(text: &str, scale: u32) -> Result<Self> {
+                Ok(join(shift_in(text, scale)?).parse()?)
+            }
+        }
+    )*};
+}
+
+implied_float!(f32, f64);
+
+#[cfg(feature = "rust_decimal")]
+impl ImpliedDecimal for rust_decimal::Decimal {
+    fn to_implied(&self, scale: u32) -> Result<String> {
+        shift_out(&self.to_string(), scale)
+    }
+
+    fn from_implied(text: &str, scale: u32) -> Result<Self> {
+        Ok(join(shift_in(text, scale)?).parse()?)
+    }
+}
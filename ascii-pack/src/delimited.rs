@@ -0,0 +1,123 @@
+use crate::{AsciiPackError, Result};
+
+/// How a delimited field is separated from what follows it, used by fields
+/// with a `delimiter` and by every field of a struct with a `separator`.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Delimited {
+    /// The text ending the field, or `None` for the last
+    /// field of a separated struct, which ends with the input.
+    pub delimiter: Option<&'static str>,
+    /// Character which may surround the value, in which case the
+    /// delimiter is part of the value. A doubled quote within a quoted
+    /// value is a literal quote.
+    pub quote: Option<char>,
+    /// Character which makes the following character part of the value.
+    pub escape: Option<char>,
+}
+
+impl Delimited {
+    /// Reads the value at the start of `input`, returning it with any quotes
+    /// and escapes removed along with the length of input it consumed,
+    /// including the delimiter.
+    pub fn read(&self, input: &str) -> Result<(String, usize)> {
+        let quoted = self.quote.is_some() && input.chars().next() == self.quote;
+        let mut chars = input.char_indices().skip(quoted as usize).peekable();
+        let mut in_quotes = quoted;
+        let mut value = String::new();
+
+        while let Some((index, c)) = chars.next() {
+            if Some(c) == self.escape {
+                match chars.next() {
+                    Some((_, escaped)) => value.push(escaped),
+                    None => {
+                        return Err(AsciiPackError::Unpack(
+                            "The input ends with an escape character".to_owned(),
+                        ))
+                    }
+                }
+                continue;
+            }
+
+            if in_quotes {
+                if Some(c) != self.quote {
+                    value.push(c);
+                } else if chars.peek().map(|(_, next)| Some(*next)) == Some(self.quote) {
+                    chars.next();
+                    value.push(c);
+                } else {
+                    in_quotes = false;
+                }
+                continue;
+            }
+
+            if let Some(delimiter) = self.delimiter {
+                if input[index..].starts_with(delimiter) {
+                    return Ok((value, index + delimiter.len()));
+                }
+            }
+            if quoted {
+                return Err(AsciiPackError::Unpack(format!(
+                    "Unexpected '{}' after the closing quote at offset {}",
+                    c, index
+                )));
+            }
+            value.push(c);
+        }
+
+        if in_quotes {
+            return Err(AsciiPackError::Unpack(
+                "The input ends within a quoted value".to_owned(),
+            ));
+        }
+        match self.delimiter {
+            Some(delimiter) => Err(AsciiPackError::Unpack(format!(
+                "The delimiter '{}' was not found",
+                delimiter
+            ))),
+            None => Ok((value, input.len())),
+        }
+    }
+
+    /// Writes `value` followed by the delimiter onto `result`, quoting or
+    /// escaping the value when it contains the delimiter or the quote or
+    /// escape characters.
+    pub fn write(&self, value: &str, result: &mut String) -> Result<()> {
+        let special = |c: char| Some(c) == self.quote || Some(c) == self.escape;
+        let contains_delimiter = self
+            .delimiter
+            .is_some_and(|delimiter| value.contains(delimiter));
+
+        if !contains_delimiter && !value.chars().any(special) {
+            result.push_str(value);
+        } else if let Some(quote) = self.quote {
+            result.push(quote);
+            for c in value.chars() {
+                if special(c) {
+                    result.push(self.escape.unwrap_or(quote));
+                }
+                result.push(c);
+            }
+            result.push(quote);
+        } else if let Some(escape) = self.escape {
+            for (index, c) in value.char_indices() {
+                let starts_delimiter = self
+                    .delimiter
+                    .is_some_and(|delimiter| value[index..].starts_with(delimiter));
+                if special(c) || starts_delimiter {
+                    result.push(escape);
+                }
+                result.push(c);
+            }
+        } else {
+            return Err(AsciiPackError::Pack(format!(
+                "'{}' contains the delimiter, but there is no quote or escape character",
+                value
+            )));
+        }
+
+        if let Some(delimiter) = self.delimiter {
+            result.push_str(delimiter);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use crate::Align;
+
+/// The AsciiPack attribute a field was declared with.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum FieldKind {
+    /// A `#[pack]` field holding a single value.
+    Pack,
+    /// A `#[pack_vec]` field holding repeated values.
+    PackVec,
+    /// A `#[pack_static]` field holding a literal.
+    PackStatic,
+    /// A `#[pack_ignore]` field which is not packed at all.
+    PackIgnore,
+    /// A variant of a derived enum. Its tag or code is the `text` of the
+    /// descriptor, and the layout of its fields is `nested`.
+    Variant,
+}
+
+/// Describes a single field of a derived type, as returned by [`Layout`].
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct FieldLayout {
+    /// The name of the field, or its index for tuple fields.
+    pub name: &'static str,
+    /// The attribute the field was declared with.
+    pub kind: FieldKind,
+    /// The number of characters the field occupies, or each of its items
+    /// occupies for `pack_vec` fields, when that is known.
+    pub size: Option<usize>,
+    /// The offset of the field from the start of the packed type. This is
+    /// only known when every preceding field is statically sized.
+    pub offset: Option<usize>,
+    /// The alignment of the value within a sized field.
+    pub align: Option<Align>,
+    /// The character a sized field is padded with.
+    pub pad: Option<char>,
+    /// The literal text of a `pack_static` field, or the tag or
+    /// code of an enum variant.
+    pub text: Option<&'static str>,
+    /// The Rust type of the field, as written in its declaration.
+    pub type_name: &'static str,
+    /// The layout of the fields of a nested derived type, or of each
+    /// item for `pack_vec` fields. This is empty for scalar types.
+    pub nested: &'static [FieldLayout],
+}
+
+/// Runtime layout introspection for types deriving `AsciiPack`, describing
+/// every field in packing order. This lets tooling such as spec sheets,
+/// viewers and validators be built from the record types themselves.
+pub trait Layout {
+    /// Returns the descriptors of the fields of this type in packing order.
+    fn layout() -> &'static [FieldLayout];
+}
@@ -1,18 +1,74 @@
 use std::{
     char::ParseCharError,
     convert::Infallible,
+    fmt::Display,
     num::{ParseFloatError, ParseIntError},
+    ops::Range,
     str::{FromStr, ParseBoolError},
 };
 use thiserror::Error;
 
 pub use ascii_pack_macro::*;
+pub use boolean::BoolFormat;
+pub use date::{DateFormat, DateParts, PackDate};
+pub use decimal::ImpliedDecimal;
+pub use delimited::Delimited;
+pub use layout::{FieldKind, FieldLayout, Layout};
+pub use number::{Radix, Separators};
+pub use report::{AsciiPackReport, Diagnostic};
+pub use sign::Sign;
 pub use strum;
 pub mod until;
 
+mod boolean;
+mod date;
+mod decimal;
+mod delimited;
+mod layout;
+mod number;
+mod report;
+mod sign;
+
 pub type Result<T> = std::result::Result<T, AsciiPackError>;
 
 pub trait AsciiPack {
+    /// The number of characters every value of this type packs to, or `None`
+    /// when the packed width varies. Derived structs compute this at compile
+    /// time, and it is `Some` whenever every field is statically sized.
+    ///
+    /// A field declaring a size which disagrees with the `SIZE` of its type
+    /// fails to compile:
+    ///
+    /// ```compile_fail
+    /// use ascii_pack::AsciiPack;
+    ///
+    /// #[derive(AsciiPack, Default)]
+    /// pub struct Inner {
+    ///     #[pack(size = 5)]
+    ///     pub name: String,
+    ///     #[pack(size = 4)]
+    ///     pub number: u32,
+    /// }
+    ///
+    /// #[derive(AsciiPack, Default)]
+    /// pub struct Outer {
+    ///     #[pack(size = 8)]
+    ///     pub inner: Inner,
+    /// }
+    /// ```
+    const SIZE: Option<usize> = None;
+
+    /// The layout of the fields of derived types, which is empty for scalar
+    /// types. This backs the [`Layout`] impl of derived types, and allows
+    /// their layouts to include the layouts of nested types.
+    const LAYOUT: &'static [FieldLayout] = &[];
+
+    /// The character sized fields of this type are padded with when they do
+    /// not declare a pad. Derived code enums are padded with spaces, which
+    /// they ignore when unpacking, so that their codes are never mistaken
+    /// for padding.
+    const PAD: char = '0';
+
     fn from_ascii(input: &str) -> Result<Self>
     where
         Self: Sized;
@@ -45,10 +101,265 @@ pub enum AsciiPackError {
     Infallible(#[from] Infallible),
     #[error("Strum parse error")]
     StrumParseError(#[from] strum::ParseError),
+    #[cfg(feature = "rust_decimal")]
+    #[error("parse decimal failed")]
+    DecimalError(#[from] rust_decimal::Error),
+    #[error("expected static text {expected:?} at offset {offset}, found {found:?}")]
+    StaticMismatch {
+        expected: &'static str,
+        found: String,
+        offset: usize,
+    },
+    #[error("{field} needs {size} characters at offset {offset}, but only {available} remain")]
+    Truncated {
+        field: &'static str,
+        offset: usize,
+        size: usize,
+        available: usize,
+    },
+    #[error("{field} does not start or end on a character boundary at offset {offset}")]
+    CharBoundary { field: &'static str, offset: usize },
+    #[error("{remaining} unconsumed characters remained at offset {offset}")]
+    TrailingInput { remaining: usize, offset: usize },
+    #[error("{} ({type_name}) at {range:?} {text:?}: {source}", format_path(.path))]
+    Context {
+        /// Path of the field which failed, from the outermost type.
+        path: Vec<PathSegment>,
+        /// Range of `text` within the input of the outermost type.
+        range: Range<usize>,
+        /// The text which the innermost field was unpacked from.
+        text: String,
+        /// The type of the innermost field.
+        type_name: &'static str,
+        #[source]
+        source: Box<AsciiPackError>,
+    },
+    #[error("no variant of {name} matched: {}", variant_errors(.errors))]
+    NoMatchingVariant {
+        name: &'static str,
+        errors: Vec<(&'static str, AsciiPackError)>,
+    },
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+/// A segment of the path of the field an error occurred in.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum PathSegment {
+    /// A named or numbered field.
+    Field(&'static str),
+    /// An item of a `pack_vec` field.
+    Index(usize),
+}
+
+impl AsciiPackError {
+    /// Adds the context of the value at `offset` within `input` which failed
+    /// to unpack with this error. Derived code adds the context of each field
+    /// and `pack_vec` item, so that the context of a nested error describes
+    /// its full path and its range within the outermost input.
+    pub fn context(
+        self,
+        segment: PathSegment,
+        input: &str,
+        offset: usize,
+        size: Option<usize>,
+        type_name: &'static str,
+    ) -> AsciiPackError {
+        match self {
+            AsciiPackError::Context {
+                mut path,
+                range,
+                text,
+                type_name,
+                source,
+            } => {
+                path.insert(0, segment);
+                AsciiPackError::Context {
+                    path,
+                    range: range.start + offset..range.end + offset,
+                    text,
+                    type_name,
+                    source,
+                }
+            }
+            error => {
+                let rest = input.get(offset..).unwrap_or_default();
+                let end = size.map_or(rest.len(), |size| size.min(rest.len()));
+                let text = rest.get(..end).unwrap_or(rest);
+                AsciiPackError::Context {
+                    path: vec![segment],
+                    range: offset..offset + text.len(),
+                    text: text.to_owned(),
+                    type_name,
+                    source: Box::new(error),
+                }
+            }
+        }
+    }
+
+    /// The error beneath any added context.
+    pub fn root_cause(&self) -> &AsciiPackError {
+        match self {
+            AsciiPackError::Context { source, .. } => source.root_cause(),
+            error => error,
+        }
+    }
+}
+
+/// Formats a field path as it would be written in rust, e.g. `days[2].id`.
+fn format_path(path: &[PathSegment]) -> String {
+    let mut formatted = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Field(name) => {
+                if !formatted.is_empty() {
+                    formatted.push('.');
+                }
+                formatted.push_str(name);
+            }
+            PathSegment::Index(index) => formatted.push_str(&format!("[{index}]")),
+        }
+    }
+    formatted
+}
+
+/// Sums the packed widths of fields which are packed one after another.
+/// The total is only known when the width of every field is known.
+pub const fn sum_sizes(sizes: &[Option<usize>]) -> Option<usize> {
+    let mut total = 0;
+    let mut i = 0;
+    while i < sizes.len() {
+        match sizes[i] {
+            Some(size) => total += size,
+            None => return None,
+        }
+        i += 1;
+    }
+    Some(total)
+}
+
+/// Returns the packed width of `count` items which are each `size`
+/// characters wide, or `None` when the width of the items is unknown.
+pub const fn repeat_size(count: usize, size: Option<usize>) -> Option<usize> {
+    match size {
+        Some(size) => Some(count * size),
+        None => None,
+    }
+}
+
+/// Returns the packed width shared by every variant of an enum,
+/// or `None` when the variants do not all pack to the same width.
+pub const fn common_size(sizes: &[Option<usize>]) -> Option<usize> {
+    if sizes.is_empty() {
+        return None;
+    }
+
+    let mut i = 1;
+    while i < sizes.len() {
+        match (sizes[0], sizes[i]) {
+            (Some(first), Some(size)) if first == size => {}
+            _ => return None,
+        }
+        i += 1;
+    }
+    sizes[0]
+}
+
+/// Returns the `size` characters of `input` starting at `offset`, which
+/// are unpacked as `field`. Used by derived code in place of indexing, so
+/// that short or malformed input is reported instead of panicking.
+pub fn field_slice<'a>(
+    input: &'a str,
+    field: &'static str,
+    offset: usize,
+    size: usize,
+) -> Result<&'a str> {
+    let available = input.len().saturating_sub(offset);
+    if size > available || offset > input.len() {
+        return Err(AsciiPackError::Truncated {
+            field,
+            offset,
+            size,
+            available,
+        });
+    }
+
+    input
+        .get(offset..offset + size)
+        .ok_or(AsciiPackError::CharBoundary { field, offset })
+}
+
+/// Returns up to `size` characters of `input` starting at `offset`, which
+/// is shorter than `size` at the end of the input. This is the chunk that
+/// the `until` function of a sized `pack_vec` field is called with.
+pub fn field_chunk<'a>(
+    input: &'a str,
+    field: &'static str,
+    offset: usize,
+    size: usize,
+) -> Result<&'a str> {
+    let size = size.min(input.len().saturating_sub(offset));
+    field_slice(input, field, offset, size)
+}
+
+/// Returns the remainder of `input` starting at `offset`,
+/// from which the unsized `field` is unpacked.
+pub fn field_rest<'a>(input: &'a str, field: &'static str, offset: usize) -> Result<&'a str> {
+    field_chunk(input, field, offset, usize::MAX)
+}
+
+/// Whether `text` only holds the `blank` fill character, which an
+/// `Option` field unpacks as `None`.
+pub fn is_blank(text: &str, blank: char) -> bool {
+    text.chars().all(|c| c == blank)
+}
+
+/// The `size` characters of `blank` fill an `Option` field packs `None` as.
+pub fn blank(size: usize, blank: char) -> String {
+    blank.to_string().repeat(size)
+}
+
+/// Pads the text of the field `name` like [`Align::pad`]. Negative numbers
+/// cannot be padded with digits, since `00-12` does not unpack, so they
+/// require the `sign` option of the field to write their sign.
+pub fn pad_field(align: Align, text: &str, size: usize, pad: char, name: &str) -> Result<String> {
+    if text.starts_with('-') && pad.is_ascii_digit() && text.len() < size {
+        return Err(AsciiPackError::Pack(format!(
+            "{name} is negative, which requires a sign option to be padded with '{pad}': '{text}'"
+        )));
+    }
+    Ok(align.pad(text, size, pad))
+}
+
+/// Converts the value unpacked as the `as` type of a field into the
+/// type of the field.
+pub fn convert_from<U, T>(value: U) -> Result<T>
+where
+    T: TryFrom<U>,
+    T::Error: Display,
+{
+    T::try_from(value).map_err(|e| AsciiPackError::Unpack(e.to_string()))
+}
+
+/// Converts the value of a field into its `as` type for packing.
+pub fn convert_into<T, U>(value: &T) -> Result<U>
+where
+    T: Clone,
+    U: TryFrom<T>,
+    U::Error: Display,
+{
+    U::try_from(value.clone()).map_err(|e| AsciiPackError::Pack(e.to_string()))
+}
+
+/// Formats the per-variant failures of an untagged enum.
+fn variant_errors(errors: &[(&'static str, AsciiPackError)]) -> String {
+    errors
+        .iter()
+        .map(|(variant, error)| format!("{variant}: {error}"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 impl<T> AsciiPack for T
 where
     T: FromStr + ToString,
@@ -75,6 +386,90 @@ where
     }
 }
 
+/// Position of a value within a sized field that is wider than the value,
+/// selected with the `align` option of `pack` and `pack_vec` fields.
+#[derive(Default, Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Align {
+    /// The value comes first, followed by the padding.
+    Left,
+    /// The padding comes first, followed by the value.
+    #[default]
+    Right,
+    /// The padding is split around the value, with any odd
+    /// padding character placed after the value.
+    Center,
+}
+
+impl Align {
+    /// Pads `value` with the `pad` character up to `size` characters.
+    /// Values which are already `size` characters or longer are
+    /// returned unchanged.
+    pub fn pad(self, value: &str, size: usize, pad: char) -> String {
+        let padding = size.saturating_sub(value.len());
+        let (before, after) = match self {
+            Align::Left => (0, padding),
+            Align::Right => (padding, 0),
+            Align::Center => (padding / 2, padding - padding / 2),
+        };
+
+        let mut result = String::with_capacity(size);
+        result.push_str(&pad.to_string().repeat(before));
+        result.push_str(value);
+        result.push_str(&pad.to_string().repeat(after));
+        result
+    }
+
+    /// Removes the `pad` characters added by [`Align::pad`]
+    /// from the padded side(s) of `value`. A value made up only of a digit
+    /// pad keeps one of them, since it is the value itself, such as a zero
+    /// padded with zeros.
+    pub fn trim(self, value: &str, pad: char) -> &str {
+        let trimmed = match self {
+            Align::Left => value.trim_end_matches(pad),
+            Align::Right => value.trim_start_matches(pad),
+            Align::Center => value.trim_matches(pad),
+        };
+        match trimmed.is_empty() && pad.is_ascii_digit() && !value.is_empty() {
+            true => &value[value.len() - pad.len_utf8()..],
+            false => trimmed,
+        }
+    }
+}
+
+/// What `from_ascii` accepts after the last field of a struct, selected
+/// with `#[pack_struct(trailing = "...")]`. Unsized unpacking is unaffected,
+/// since the remaining input belongs to whatever follows the struct.
+#[derive(Default, Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Trailing {
+    /// Any remaining input is an error.
+    #[default]
+    Error,
+    /// Remaining input is discarded.
+    Ignore,
+    /// Remaining input is discarded if it is only whitespace.
+    Whitespace,
+}
+
+impl Trailing {
+    /// Checks the input remaining after the first `consumed` characters.
+    pub fn check(self, input: &str, consumed: usize) -> Result<()> {
+        let rest = input.get(consumed..).unwrap_or_default();
+        let unexpected = match self {
+            Trailing::Error => rest,
+            Trailing::Ignore => "",
+            Trailing::Whitespace => rest.trim_start(),
+        };
+
+        match unexpected.is_empty() {
+            true => Ok(()),
+            false => Err(AsciiPackError::TrailingInput {
+                remaining: unexpected.len(),
+                offset: input.len() - unexpected.len(),
+            }),
+        }
+    }
+}
+
 /// This (empty) struct represents a statically-sized ascii field.
 /// It's text representation is derived from the `pack_static` attribute
 /// assigned to the field definition, and it otherwise contains no data.
@@ -0,0 +1,113 @@
+use crate::Result;
+
+/// Integers packed in another base, selected with the `radix`
+/// option of `pack` fields. Negative numbers are written with a
+/// `-` before their magnitude rather than in two's complement.
+///
+/// Like `from_str_radix`, both methods panic when `radix` is
+/// not between 2 and 36, which the derive checks for.
+pub trait Radix: Sized {
+    /// Writes the value in base `radix`, with upper case digits when `upper`.
+    fn to_radix(&self, radix: u32, upper: bool) -> String;
+
+    /// Reads a value written in base `radix`, with digits of either case.
+    fn from_radix(text: &str, radix: u32) -> Result<Self>;
+}
+
+/// Writes the digits of `magnitude` in base `radix`.
+fn radix_digits(negative: bool, mut magnitude: u128, radix: u32, upper: bool) -> String {
+    let mut digits = Vec::new();
+    loop {
+        let digit = char::from_digit((magnitude % radix as u128) as u32, radix)
+            .expect("radix is between 2 and 36");
+        digits.push(match upper {
+            true => digit.to_ascii_uppercase(),
+            false => digit,
+        });
+        magnitude /= radix as u128;
+        if magnitude == 0 {
+            break;
+        }
+    }
+    if negative {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
+}
+
+macro_rules! radix_signed {
+    ($($ty:ty),*) => {$(
+        impl Radix for $ty {
+            fn to_radix(&self, radix: u32, upper: bool) -> String {
+                radix_digits(*self < 0, self.unsigned_abs() as u128, radix, upper)
+            }
+
+            fn from_radix(text: &str, radix: u32) -> Result<Self> {
+                Ok(<$ty>::from_str_radix(text, radix)?)
+            }
+        }
+    )*};
+}
+
+macro_rules! radix_unsigned {
+    ($($ty:ty),*) => {$(
+        impl Radix for $ty {
+            fn to_radix(&self, radix: u32, upper: bool) -> String {
+                radix_digits(false, *self as u128, radix, upper)
+            }
+
+            fn from_radix(text: &str, radix: u32) -> Result<Self> {
+                Ok(<$ty>::from_str_radix(text, radix)?)
+            }
+        }
+    )*};
+}
+
+radix_signed!(i8, i16, i32, i64, i128, isize);
+radix_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// The decimal separator and thousands grouping of a number, selected with
+/// the `decimal` and `grouping` options of `pack` fields.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub struct Separators {
+    /// The character written in place of the decimal point.
+    pub decimal: char,
+    /// The character written between each group of three whole digits.
+    pub grouping: Option<char>,
+}
+
+impl Separators {
+    /// Writes the number `text`, as written by `to_ascii`, with the separators.
+    pub fn write(&self, text: &str) -> String {
+        let digits_start = text.find(|c: char| c.is_ascii_digit()).unwrap_or(0);
+        let (sign, digits) = text.split_at(digits_start);
+        let (whole, fraction) = match digits.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (digits, None),
+        };
+
+        let mut result = sign.to_owned();
+        for (index, c) in whole.chars().enumerate() {
+            if let Some(grouping) = self.grouping {
+                if index > 0 && (whole.len() - index) % 3 == 0 {
+                    result.push(grouping);
+                }
+            }
+            result.push(c);
+        }
+        if let Some(fraction) = fraction {
+            result.push(self.decimal);
+            result.push_str(fraction);
+        }
+        result
+    }
+
+    /// Reads a number written with the separators into the form
+    /// `from_ascii` reads.
+    pub fn read(&self, text: &str) -> String {
+        text.chars()
+            .filter(|&c| Some(c) != self.grouping)
+            .map(|c| if c == self.decimal { '.' } else { c })
+            .collect()
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{AsciiPack, AsciiPackError};
+
+/// A failure found while unpacking a record with
+/// [`AsciiPackReport::from_ascii_report`].
+#[derive(Debug)]
+pub struct Diagnostic {
+    /// The field which failed to unpack, or `None` for failures of the
+    /// record as a whole, such as trailing input.
+    pub field: Option<&'static str>,
+    /// Offset of the field within the input.
+    pub offset: usize,
+    /// Why the field failed to unpack, along with its context.
+    pub error: AsciiPackError,
+}
+
+/// Unpacking which carries on past failing fields to report every failure
+/// in a record at once. Derived for structs alongside `AsciiPack`.
+///
+/// A field which fails to unpack is left at its default value, and unpacking
+/// resumes after it when its size is fixed. Since the end of a failing field
+/// of variable size is unknown, unpacking stops there instead.
+pub trait AsciiPackReport: AsciiPack {
+    /// Unpacks as much of `input` as possible, returning the partially
+    /// populated value along with the failures found.
+    fn from_ascii_report(input: &str) -> (Self, Vec<Diagnostic>)
+    where
+        Self: Sized;
+
+    /// Returns the failures found unpacking `input`, which is
+    /// valid when there are none.
+    fn validate_ascii(input: &str) -> Vec<Diagnostic>
+    where
+        Self: Sized,
+    {
+        Self::from_ascii_report(input).1
+    }
+}
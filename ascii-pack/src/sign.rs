@@ -0,0 +1,125 @@
+use crate::{Align, AsciiPackError, Result};
+
+/// Overpunched last digits of positive numbers, indexed by the digit.
+const POSITIVE: [char; 10] = ['{', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I'];
+/// Overpunched last digits of negative numbers, indexed by the digit.
+const NEGATIVE: [char; 10] = ['}', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R'];
+
+/// Where the sign of a number is written, selected with the `sign` option
+/// of `pack` fields. The padding of the field goes between the sign and
+/// the digits, so `-12` packs as `-0012` in a field of 5 characters.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum Sign {
+    /// A `-` before the digits of negative numbers, such as `-0012`.
+    Leading,
+    /// A `-` after the digits of negative numbers, such as `0012-`.
+    Trailing,
+    /// COBOL zoned decimal, where the last digit is replaced by a letter
+    /// holding both the digit and the sign, such as `001K` for `-12`.
+    Overpunch,
+}
+
+impl Sign {
+    /// Packs `text`, a number as written by `to_ascii`, with its digits padded
+    /// to `size` characters including the sign. With `plus`, positive numbers
+    /// are written with a `+`, or with positive overpunch letters.
+    pub fn pack(
+        self,
+        text: &str,
+        plus: bool,
+        size: Option<usize>,
+        align: Align,
+        pad: char,
+    ) -> Result<String> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let sign = match (negative, plus) {
+            (true, _) => Some('-'),
+            (false, true) => Some('+'),
+            (false, false) => None,
+        };
+
+        let sign_len = match self {
+            Sign::Overpunch => 0,
+            _ => sign.map_or(0, char::len_utf8),
+        };
+        let mut digits = match size {
+            Some(size) => align.pad(digits, size.saturating_sub(sign_len), pad),
+            None => digits.to_owned(),
+        };
+
+        match (self, sign) {
+            (_, None) => {}
+            (Sign::Leading, Some(sign)) => digits.insert(0, sign),
+            (Sign::Trailing, Some(sign)) => digits.push(sign),
+            (Sign::Overpunch, Some(_)) => {
+                let last = digits.pop().and_then(|c| c.to_digit(10)).ok_or_else(|| {
+                    AsciiPackError::Pack(format!(
+                        "'{}' does not end with a digit to overpunch",
+                        text
+                    ))
+                })?;
+                let letters = if negative { NEGATIVE } else { POSITIVE };
+                digits.push(letters[last as usize]);
+            }
+        }
+        Ok(digits)
+    }
+
+    /// Unpacks the number in `text` into the form `from_ascii` reads,
+    /// with a leading `-` for negative numbers. With `trim`, the padding
+    /// is trimmed from the digits.
+    pub fn unpack(self, text: &str, trim: bool, align: Align, pad: char) -> Result<String> {
+        let (negative, digits) = match self {
+            Sign::Leading => match text.strip_prefix('-') {
+                Some(digits) => (true, digits.to_owned()),
+                None => (false, text.strip_prefix('+').unwrap_or(text).to_owned()),
+            },
+            Sign::Trailing => match text.strip_suffix('-') {
+                Some(digits) => (true, digits.to_owned()),
+                None => (false, text.strip_suffix('+').unwrap_or(text).to_owned()),
+            },
+            Sign::Overpunch => {
+                let mut digits = text.to_owned();
+                let negative = match digits.pop() {
+                    Some(c) if c.is_ascii_digit() => {
+                        digits.push(c);
+                        false
+                    }
+                    Some(c) => {
+                        let (negative, digit) = overpunched_digit(c).ok_or_else(|| {
+                            AsciiPackError::Unpack(format!(
+                                "'{}' does not end with a digit or an overpunched digit",
+                                text
+                            ))
+                        })?;
+                        digits.push(digit);
+                        negative
+                    }
+                    None => false,
+                };
+                (negative, digits)
+            }
+        };
+
+        let digits = match trim {
+            true => align.trim(&digits, pad),
+            false => &digits,
+        };
+        Ok(match negative {
+            true => format!("-{}", digits),
+            false => digits.to_owned(),
+        })
+    }
+}
+
+/// The sign and digit of an overpunched last digit.
+fn overpunched_digit(c: char) -> Option<(bool, char)> {
+    let (negative, digit) = match POSITIVE.iter().position(|&p| p == c) {
+        Some(digit) => (false, digit),
+        None => (true, NEGATIVE.iter().position(|&n| n == c)?),
+    };
+    Some((negative, char::from(b'0' + digit as u8)))
+}
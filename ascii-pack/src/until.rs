@@ -31,11 +31,11 @@ pub fn ascii_alpha(slice: &str) -> bool {
 /// Helper function for stopping when the next chunk starts
 /// with a specific delimeter
 pub fn starts_with(delimeter: &'static str) -> impl Fn(&str) -> bool {
-    return move |s| s.starts_with(delimeter);
+    move |s| s.starts_with(delimeter)
 }
 
 /// Helper function for stopping when there are no more
 /// characters left to consume.
 pub fn empty(slice: &str) -> bool {
-    slice.len() == 0
+    slice.is_empty()
 }
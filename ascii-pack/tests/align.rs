@@ -0,0 +1,77 @@
+use ascii_pack::{until, Align, AsciiPack};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Aligned {
+    #[pack(size = 6, align = "left", pad = ' ', trim)]
+    pub left: String,
+
+    #[pack(size = 6, align = "right", pad = ' ', trim)]
+    pub right: String,
+
+    #[pack(size = 7, align = "center", pad = '*', trim)]
+    pub center: String,
+
+    #[pack(size = 6, pad_left = ' ')]
+    pub untrimmed: String,
+
+    #[pack(size = 5, align = "left", pad = ' ', trim)]
+    pub number: u32,
+
+    #[pack_vec(size = 4, align = "left", pad = '.', trim, until = until::empty)]
+    pub names: Vec<String>,
+}
+
+#[test]
+fn aligned_fields() {
+    const EXAMPLE: &str = "TEST    TEST**AB***  TEST42   AB..CDE.F...";
+
+    let aligned = Aligned::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(aligned.left, "TEST");
+    assert_eq!(aligned.right, "TEST");
+    assert_eq!(aligned.center, "AB");
+    assert_eq!(aligned.untrimmed, "  TEST");
+    assert_eq!(aligned.number, 42);
+    assert_eq!(aligned.names, vec!["AB", "CDE", "F"]);
+
+    assert_eq!(aligned.to_ascii().unwrap(), EXAMPLE);
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Zeros {
+    #[pack(size = 5, trim)]
+    pub right: u32,
+
+    #[pack(size = 3, align = "left", pad = ' ', trim)]
+    pub empty: String,
+}
+
+#[test]
+fn trimmed_zero() {
+    const EXAMPLE: &str = "00000   ";
+
+    let zeros = Zeros::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(zeros, Zeros::default());
+    assert_eq!(zeros.to_ascii().unwrap(), EXAMPLE);
+
+    // only the leading zeros are padding
+    let trailing = Zeros {
+        right: 1200,
+        ..Default::default()
+    };
+    assert_eq!(trailing.to_ascii().unwrap(), "01200   ");
+    assert_eq!(Zeros::from_ascii("01200   ").unwrap(), trailing);
+}
+
+#[test]
+fn align_pad_and_trim() {
+    assert_eq!(Align::Left.pad("AB", 5, ' '), "AB   ");
+    assert_eq!(Align::Right.pad("AB", 5, '0'), "000AB");
+    assert_eq!(Align::Center.pad("AB", 5, '-'), "-AB--");
+    assert_eq!(Align::Center.pad("ABCDEF", 5, '-'), "ABCDEF");
+
+    assert_eq!(Align::Left.trim("AB   ", ' '), "AB");
+    assert_eq!(Align::Right.trim("  AB  ", ' '), "AB  ");
+    assert_eq!(Align::Center.trim("-AB--", '-'), "AB");
+    assert_eq!(Align::Right.trim("000", '0'), "0");
+    assert_eq!(Align::Left.trim("   ", ' '), "");
+}
@@ -0,0 +1,75 @@
+use ascii_pack::AsciiPack;
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Flags {
+    #[pack(size = 1, bool = ("Y", "N"))]
+    pub active: bool,
+
+    #[pack(size = 1, bool = ("1", "0"))]
+    pub deleted: bool,
+
+    #[pack(size = 1, bool = ("T", "F"), ignore_case)]
+    pub verified: bool,
+
+    #[pack(size = 1, bool = ("Y", "N"))]
+    pub consent: Option<bool>,
+
+    #[pack(size = 3, bool = ("YES", "NO"), align = "left", pad = ' ', trim)]
+    pub answer: bool,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Delimited {
+    #[pack(delimiter = ";", bool = ("true", "false"), ignore_case)]
+    pub first: bool,
+
+    #[pack(delimiter = ";", bool = ("on", "off"))]
+    pub second: Option<bool>,
+}
+
+#[test]
+fn round_trip() {
+    const EXAMPLE: &str = "Y0TNNO ";
+    let unpacked = Flags::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(
+        unpacked,
+        Flags {
+            active: true,
+            deleted: false,
+            verified: true,
+            consent: Some(false),
+            answer: false,
+        }
+    );
+    assert_eq!(unpacked.to_ascii().unwrap(), EXAMPLE);
+}
+
+#[test]
+fn ignore_case() {
+    let unpacked = Flags::from_ascii("N1fYYES").unwrap();
+    assert!(!unpacked.verified);
+    assert_eq!(unpacked.to_ascii().unwrap(), "N1FYYES");
+
+    let unpacked = Delimited::from_ascii("TRUE;off;").unwrap();
+    assert!(unpacked.first);
+    assert_eq!(unpacked.second, Some(false));
+    assert_eq!(unpacked.to_ascii().unwrap(), "true;off;");
+}
+
+#[test]
+fn blank_is_none() {
+    let unpacked = Flags::from_ascii("Y1T NO ").unwrap();
+    assert_eq!(unpacked.consent, None);
+    assert_eq!(unpacked.to_ascii().unwrap(), "Y1T NO ");
+
+    let unpacked = Delimited::from_ascii("false;;").unwrap();
+    assert_eq!(unpacked.second, None);
+}
+
+#[test]
+fn other_text_is_an_error() {
+    assert!(Flags::from_ascii("X1TYYES").is_err());
+    assert!(Flags::from_ascii("y1TYYES").is_err());
+    assert!(Flags::from_ascii("Y1TYMAY").is_err());
+    assert!(Delimited::from_ascii("true;ON;").is_err());
+}
@@ -0,0 +1,87 @@
+use ascii_pack::AsciiPack;
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default, Clone)]
+enum Kind {
+    #[pack(code = "FAKE")]
+    Fake,
+    #[pack(code = "REAL")]
+    Real,
+    #[default]
+    #[pack(code = "UNKNOWN")]
+    Unknown,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default, Clone)]
+#[pack_enum(ignore_case)]
+enum Currency {
+    #[default]
+    #[pack(code = "USD")]
+    Usd,
+    #[pack(code = "EUR")]
+    Eur,
+    #[pack(other)]
+    Other(String),
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+struct Payment {
+    #[pack(size = 7, pad_left = ' ')]
+    pub kind: Kind,
+
+    #[pack(size = 4, pad_left = ' ')]
+    pub currency: Currency,
+
+    #[pack(size = 6)]
+    pub amount: u32,
+}
+
+#[test]
+fn code_enum() {
+    assert_eq!(Kind::from_ascii("FAKE").unwrap(), Kind::Fake);
+    assert_eq!(Kind::from_ascii("   REAL").unwrap(), Kind::Real);
+    assert_eq!(Kind::Real.to_ascii().unwrap(), "REAL");
+    assert!(Kind::from_ascii("fake").is_err());
+    assert!(Kind::from_ascii("OTHER").is_err());
+}
+
+#[test]
+fn code_enum_other() {
+    assert_eq!(Currency::from_ascii("usd").unwrap(), Currency::Usd);
+    assert_eq!(Currency::from_ascii(" EUR").unwrap(), Currency::Eur);
+    assert_eq!(
+        Currency::from_ascii(" GBP").unwrap(),
+        Currency::Other("GBP".to_owned())
+    );
+    assert_eq!(Currency::Other("GBP".to_owned()).to_ascii().unwrap(), "GBP");
+}
+
+#[test]
+fn code_enum_field() {
+    const EXAMPLE: &str = "   FAKE GBP001250";
+
+    let payment = Payment::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(payment.kind, Kind::Fake);
+    assert_eq!(payment.currency, Currency::Other("GBP".to_owned()));
+    assert_eq!(payment.amount, 1250);
+
+    assert_eq!(payment.to_ascii().unwrap(), EXAMPLE);
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+struct Transfer {
+    #[pack(size = 6)]
+    pub kind: Kind,
+
+    #[pack(size = 5, align = "left")]
+    pub currency: Currency,
+}
+
+#[test]
+fn code_enum_default_padding() {
+    let transfer = Transfer {
+        kind: Kind::Real,
+        currency: Currency::Eur,
+    };
+    assert_eq!(transfer.to_ascii().unwrap(), "  REALEUR  ");
+    assert_eq!(Transfer::from_ascii("  REALEUR  ").unwrap(), transfer);
+}
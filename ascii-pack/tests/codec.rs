@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use ascii_pack::{until, AsciiPack, AsciiPackError};
+
+/// Durations packed as hours and minutes, such as "0130"
+mod hhmm {
+    use std::time::Duration;
+
+    use ascii_pack::{AsciiPackError, Result};
+
+    pub fn from_ascii(text: &str) -> Result<Duration> {
+        let (hours, minutes) = text.split_at(2);
+        let (hours, minutes): (u64, u64) = (hours.parse()?, minutes.parse()?);
+        if minutes >= 60 {
+            return Err(AsciiPackError::Unpack(format!("'{}' is not a time", text)));
+        }
+        Ok(Duration::from_secs((hours * 60 + minutes) * 60))
+    }
+
+    pub fn to_ascii(value: &Duration) -> Result<String> {
+        let minutes = value.as_secs() / 60;
+        Ok(format!("{:02}{:02}", minutes / 60, minutes % 60))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy)]
+pub struct AccountId(u32);
+
+impl From<u32> for AccountId {
+    fn from(value: u32) -> Self {
+        AccountId(value)
+    }
+}
+
+impl From<AccountId> for u32 {
+    fn from(value: AccountId) -> Self {
+        value.0
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy)]
+pub struct Percent(u8);
+
+impl TryFrom<u16> for Percent {
+    type Error = String;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match u8::try_from(value) {
+            Ok(value) if value <= 100 => Ok(Percent(value)),
+            _ => Err(format!("{} is not a percentage", value)),
+        }
+    }
+}
+
+impl From<Percent> for u16 {
+    fn from(value: Percent) -> Self {
+        value.0.into()
+    }
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Shift {
+    #[pack(size = 6, as = u32)]
+    pub account: AccountId,
+
+    #[pack(size = 4, with = hhmm)]
+    pub length: Duration,
+
+    #[pack(size = 3, as = u16)]
+    pub share: Option<Percent>,
+
+    #[pack(size = 4, as = u32, radix = 16, case = "upper")]
+    pub badge: AccountId,
+
+    #[pack_vec(size = 4, count = 2, with = hhmm)]
+    pub breaks: Vec<Duration>,
+
+    #[pack_vec(size = 6, until = until::empty, as = u32)]
+    pub reports: Vec<AccountId>,
+}
+
+const EXAMPLE: &str = "0000420830100BEEF00150045000007000011";
+
+#[test]
+fn round_trip() {
+    let unpacked = Shift::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(
+        unpacked,
+        Shift {
+            account: AccountId(42),
+            length: Duration::from_secs(8 * 3600 + 30 * 60),
+            share: Some(Percent(100)),
+            badge: AccountId(0xBEEF),
+            breaks: vec![Duration::from_secs(15 * 60), Duration::from_secs(45 * 60)],
+            reports: vec![AccountId(7), AccountId(11)],
+        }
+    );
+    assert_eq!(unpacked.to_ascii().unwrap(), EXAMPLE);
+}
+
+#[test]
+fn blank_option() {
+    let unpacked = Shift::from_ascii("0000420830   BEEF00150045").unwrap();
+    assert_eq!(unpacked.share, None);
+    assert!(unpacked.reports.is_empty());
+    assert_eq!(unpacked.to_ascii().unwrap(), "0000420830   BEEF00150045");
+}
+
+#[test]
+fn conversion_errors() {
+    let error = Shift::from_ascii("0000420830101BEEF00150045").unwrap_err();
+    assert!(
+        error.to_string().contains("101 is not a percentage"),
+        "{}",
+        error
+    );
+
+    let error = Shift::from_ascii("0000420870100BEEF00150045").unwrap_err();
+    assert!(
+        error.to_string().contains("'0870' is not a time"),
+        "{}",
+        error
+    );
+
+    let shift = Shift {
+        share: Some(Percent(101)),
+        ..Default::default()
+    };
+    assert!(matches!(shift.to_ascii(), Err(AsciiPackError::Pack(_))));
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default, Clone)]
+pub struct RawLevel {
+    #[pack(size = 3)]
+    pub value: u16,
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy)]
+pub struct Level(u16);
+
+impl From<RawLevel> for Level {
+    fn from(raw: RawLevel) -> Self {
+        Level(raw.value)
+    }
+}
+
+impl From<Level> for RawLevel {
+    fn from(level: Level) -> Self {
+        RawLevel { value: level.0 }
+    }
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Levels {
+    #[pack(as = RawLevel)]
+    pub level: Level,
+
+    #[pack(size = 2, when = result.level.0 as u32 > 0)]
+    pub bonus: u8,
+}
+
+#[test]
+fn size_of_packed_type() {
+    assert_eq!(Levels::SIZE, None);
+
+    let unpacked = Levels::from_ascii("01207").unwrap();
+    assert_eq!(unpacked.level, Level(12));
+    assert_eq!(unpacked.bonus, 7);
+    assert_eq!(unpacked.to_ascii().unwrap(), "01207");
+
+    let unpacked = Levels::from_ascii("000").unwrap();
+    assert_eq!(unpacked.bonus, 0);
+    assert_eq!(unpacked.to_ascii().unwrap(), "000");
+}
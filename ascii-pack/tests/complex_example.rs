@@ -1,4 +1,4 @@
-use ascii_pack::{until, AsciiPack, AsciiPackError, AsciiPackUnsized, Static};
+use ascii_pack::{until, AsciiPack, Static};
 use strum::{Display, EnumString};
 
 #[derive(PartialEq, Eq, Debug, Default, EnumString, Clone, Display)]
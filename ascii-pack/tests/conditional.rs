@@ -0,0 +1,66 @@
+use ascii_pack::{AsciiPack, AsciiPackReport};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Record {
+    #[pack(size = 1)]
+    pub kind: String,
+
+    #[pack(size = 4)]
+    pub id: u32,
+
+    #[pack(size = 6, when = result.kind == "R")]
+    pub refund: u32,
+
+    #[pack(size = 2, when = result.id > 100 && result.kind != "X")]
+    pub region: String,
+
+    #[pack(size = 2)]
+    pub code: String,
+}
+
+#[test]
+fn present_when_condition_holds() {
+    const EXAMPLE: &str = "R0101000250EUAB";
+    let unpacked = Record::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(unpacked.kind, "R");
+    assert_eq!(unpacked.id, 101);
+    assert_eq!(unpacked.refund, 250);
+    assert_eq!(unpacked.region, "EU");
+    assert_eq!(unpacked.code, "AB");
+    assert_eq!(unpacked.to_ascii().unwrap(), EXAMPLE);
+    assert_eq!(Record::SIZE, None);
+
+    // fields after a conditional field have no fixed offset
+    assert_eq!(Record::LAYOUT[2].size, Some(6));
+    assert_eq!(Record::LAYOUT[2].offset, Some(5));
+    assert_eq!(Record::LAYOUT[3].offset, None);
+}
+
+#[test]
+fn absent_when_condition_fails() {
+    const EXAMPLE: &str = "S0001AB";
+    let unpacked = Record::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(unpacked.refund, 0);
+    assert_eq!(unpacked.region, "");
+    assert_eq!(unpacked.code, "AB");
+    assert_eq!(unpacked.to_ascii().unwrap(), EXAMPLE);
+
+    // values of absent fields are not packed
+    let packed = Record {
+        kind: "S".to_owned(),
+        id: 1,
+        refund: 999,
+        region: "US".to_owned(),
+        code: "CD".to_owned(),
+    };
+    assert_eq!(packed.to_ascii().unwrap(), "S0001CD");
+}
+
+#[test]
+fn conditional_report() {
+    let (unpacked, diagnostics) = Record::from_ascii_report("R0101ABCDEFEUAB");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].field, Some("refund"));
+    assert_eq!(unpacked.region, "EU");
+    assert_eq!(unpacked.code, "AB");
+}
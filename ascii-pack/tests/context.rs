@@ -0,0 +1,122 @@
+use ascii_pack::{until, AsciiPack, AsciiPackError, PathSegment, Static};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Item {
+    #[pack(size = 4)]
+    pub id: usize,
+
+    #[pack_static(text = "//")]
+    pub delimeter: Static,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Day {
+    #[pack(size = 2)]
+    pub day_num: usize,
+
+    #[pack(size = 1)]
+    pub spacer: char,
+
+    #[pack_vec(until = until::starts_with("\n"))]
+    pub vec: Vec<Item>,
+
+    #[pack(size = 1)]
+    pub newline: char,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Days {
+    #[pack_static(text = "RECORD:\n")]
+    pub record: Static,
+
+    #[pack_vec(until = until::empty)]
+    pub days: Vec<Day>,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Sized {
+    #[pack(size = 2)]
+    pub count: u32,
+
+    #[pack_vec(size = 3, until = until::empty)]
+    pub values: Vec<u32>,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+pub enum Message {
+    #[pack_tag = "H"]
+    Header {
+        #[pack(size = 4)]
+        date: u32,
+    },
+}
+
+const DAYS: &str = "RECORD:
+00 0501//0443//0125//1064//
+01 0045//0002//0073//0234//
+02 0291//0342//2303//09x4//
+";
+
+#[test]
+fn nested_path() {
+    let error = Days::from_ascii(DAYS).unwrap_err();
+    match &error {
+        AsciiPackError::Context {
+            path,
+            range,
+            text,
+            type_name,
+            source,
+        } => {
+            assert_eq!(
+                path,
+                &[
+                    PathSegment::Field("days"),
+                    PathSegment::Index(2),
+                    PathSegment::Field("vec"),
+                    PathSegment::Index(3),
+                    PathSegment::Field("id"),
+                ]
+            );
+            assert_eq!(text, "09x4");
+            assert_eq!(&DAYS[range.clone()], "09x4");
+            assert_eq!(*type_name, "usize");
+            assert!(matches!(**source, AsciiPackError::Unpack(_)));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    assert!(error
+        .to_string()
+        .starts_with("days[2].vec[3].id (usize) at 85..89 \"09x4\": "));
+}
+
+#[test]
+fn sized_item_path() {
+    let error = Sized::from_ascii("03001002x03").unwrap_err();
+    match error {
+        AsciiPackError::Context {
+            path, range, text, ..
+        } => {
+            assert_eq!(path, [PathSegment::Field("values"), PathSegment::Index(2)]);
+            assert_eq!(range, 8..11);
+            assert_eq!(text, "x03");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn variant_path() {
+    let error = Message::from_ascii("H20x1").unwrap_err();
+    match error {
+        AsciiPackError::Context {
+            path, range, text, ..
+        } => {
+            assert_eq!(path, [PathSegment::Field("Header::date")]);
+            assert_eq!(range, 1..5);
+            assert_eq!(text, "20x1");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
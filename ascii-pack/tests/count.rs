@@ -0,0 +1,114 @@
+use ascii_pack::{until, AsciiPack, AsciiPackError};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Counted {
+    #[pack(size = 2)]
+    pub item_count: u8,
+
+    #[pack_vec(size = 3, count = item_count, max = 5)]
+    pub items: Vec<u32>,
+
+    #[pack(size = 2)]
+    pub trailer: String,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Rewritten {
+    #[pack(size = 2)]
+    pub item_count: usize,
+
+    #[pack_vec(size = 3, count = item_count, rewrite, min = 1)]
+    pub items: Vec<u32>,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Item {
+    #[pack(size = 2)]
+    pub id: u8,
+
+    #[pack_vec(size = 1, until = until::starts_with(";"))]
+    pub flags: Vec<char>,
+
+    #[pack(size = 1)]
+    pub end: char,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+pub enum Message {
+    #[pack_tag = "L"]
+    List {
+        #[pack(size = 1)]
+        count: u32,
+        #[pack_vec(count = count)]
+        items: Vec<Item>,
+    },
+}
+
+#[test]
+fn count_from_field() {
+    const EXAMPLE: &str = "03001002003TR";
+    let unpacked = Counted::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(unpacked.item_count, 3);
+    assert_eq!(unpacked.items, [1, 2, 3]);
+    assert_eq!(unpacked.trailer, "TR");
+    assert_eq!(unpacked.to_ascii().unwrap(), EXAMPLE);
+
+    let empty = Counted::from_ascii("00TR").unwrap();
+    assert!(empty.items.is_empty());
+}
+
+#[test]
+fn count_bounds() {
+    assert!(Counted::from_ascii("06001002003004005006TR").is_err());
+    assert!(Rewritten::from_ascii("00").is_err());
+
+    let too_many = Counted {
+        item_count: 6,
+        items: vec![1; 6],
+        trailer: "TR".to_owned(),
+    };
+    assert!(too_many.to_ascii().is_err());
+}
+
+#[test]
+fn count_mismatch() {
+    let mismatched = Counted {
+        item_count: 2,
+        items: vec![1, 2, 3],
+        trailer: "TR".to_owned(),
+    };
+    assert!(matches!(
+        mismatched.to_ascii(),
+        Err(AsciiPackError::Pack(_))
+    ));
+}
+
+#[test]
+fn count_rewrite() {
+    let rewritten = Rewritten {
+        item_count: 0,
+        items: vec![7, 8],
+    };
+    assert_eq!(rewritten.to_ascii().unwrap(), "02007008");
+    assert_eq!(
+        Rewritten::from_ascii("02007008").unwrap(),
+        Rewritten {
+            item_count: 2,
+            items: vec![7, 8],
+        }
+    );
+}
+
+#[test]
+fn count_unsized_items() {
+    const EXAMPLE: &str = "L201AB;02;";
+    let unpacked = Message::from_ascii(EXAMPLE).unwrap();
+    match &unpacked {
+        Message::List { count, items } => {
+            assert_eq!(*count, 2);
+            assert_eq!(items[0].flags, ['A', 'B']);
+            assert!(items[1].flags.is_empty());
+        }
+    }
+    assert_eq!(unpacked.to_ascii().unwrap(), EXAMPLE);
+}
@@ -0,0 +1,169 @@
+#[cfg(feature = "chrono")]
+mod chrono_dates {
+    use ascii_pack::{AsciiPack, AsciiPackError};
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+    #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+    pub struct Record {
+        #[pack(size = 8, date = "%Y%m%d")]
+        pub posted: NaiveDate,
+
+        #[pack(size = 6, date = "%y%m%d", pivot = 50)]
+        pub opened: NaiveDate,
+
+        #[pack(size = 5, date = "%y%j")]
+        pub julian: NaiveDate,
+
+        #[pack(size = 6, date = "%H%M%S")]
+        pub time: NaiveTime,
+
+        #[pack(size = 19, date = "%Y-%m-%d %H:%M:%S")]
+        pub stamp: NaiveDateTime,
+
+        #[pack(size = 8, date = "%Y%m%d")]
+        pub closed: Option<NaiveDate>,
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    const EXAMPLE: &str = "20240131491231240320304502024-01-31 13:45:0000000000";
+
+    #[test]
+    fn date_patterns() {
+        let unpacked = Record::from_ascii(EXAMPLE).unwrap();
+        assert_eq!(unpacked.posted, date(2024, 1, 31));
+        assert_eq!(unpacked.opened, date(2049, 12, 31));
+        assert_eq!(unpacked.julian, date(2024, 2, 1));
+        assert_eq!(unpacked.time, NaiveTime::from_hms_opt(3, 4, 50).unwrap());
+        assert_eq!(
+            unpacked.stamp,
+            date(2024, 1, 31).and_hms_opt(13, 45, 0).unwrap()
+        );
+        assert_eq!(unpacked.closed, None);
+
+        // None packs as spaces
+        let packed = unpacked.to_ascii().unwrap();
+        assert_eq!(&packed[..44], &EXAMPLE[..44]);
+        assert_eq!(&packed[44..], "        ");
+        assert_eq!(Record::from_ascii(&packed).unwrap(), unpacked);
+    }
+
+    #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+    pub struct Shift {
+        #[pack(size = 6, date = "%H%M%S")]
+        pub start: Option<NaiveTime>,
+
+        #[pack(size = 8, date = "%d%m%Y", blank = ' ')]
+        pub day: Option<NaiveDate>,
+    }
+
+    #[test]
+    fn midnight_is_not_blank() {
+        let midnight = Shift {
+            start: Some(NaiveTime::MIN),
+            day: None,
+        };
+        assert_eq!(midnight.to_ascii().unwrap(), "000000        ");
+        assert_eq!(Shift::from_ascii("000000        ").unwrap(), midnight);
+
+        let blank = Shift::from_ascii("      00000000").unwrap();
+        assert_eq!(blank, Shift::default());
+    }
+
+    #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+    pub struct Dated {
+        #[pack(size = 2)]
+        pub len: u8,
+
+        #[pack(size_from = len, date = "%Y%m%d")]
+        pub day: NaiveDate,
+    }
+
+    #[test]
+    fn sized_from_date() {
+        let dated = Dated {
+            len: 0,
+            day: date(2024, 1, 31),
+        };
+        assert_eq!(dated.to_ascii().unwrap(), "0820240131");
+        assert_eq!(
+            Dated::from_ascii("0820240131").unwrap(),
+            Dated { len: 8, ..dated }
+        );
+    }
+
+    #[test]
+    fn pivot_window() {
+        let mut record = Record::from_ascii(EXAMPLE).unwrap();
+        record.opened = date(1950, 1, 1);
+        assert_eq!(&record.to_ascii().unwrap()[8..14], "500101");
+
+        // years outside the window of two digit years cannot be packed
+        record.opened = date(2050, 1, 1);
+        assert!(record.to_ascii().is_err());
+    }
+
+    #[test]
+    fn invalid_dates() {
+        // February 30th
+        let error = Record::from_ascii("2024023049123124032030450").unwrap_err();
+        assert!(matches!(error.root_cause(), AsciiPackError::Unpack(_)));
+
+        // not a number
+        assert!(Record::from_ascii("2024O131").is_err());
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_dates {
+    use ascii_pack::AsciiPack;
+    use time::{Date, Month, PrimitiveDateTime, Time};
+
+    #[derive(AsciiPack, PartialEq, Eq, Debug)]
+    pub struct Record {
+        #[pack(size = 8, date = "%Y%m%d")]
+        pub posted: Date,
+
+        #[pack(size = 7, date = "%Y%j")]
+        pub julian: Date,
+
+        #[pack(size = 12, date = "%y%m%d%H%M%S")]
+        pub stamp: PrimitiveDateTime,
+
+        #[pack(size = 6, date = "%d%m%y", blank = '0')]
+        pub closed: Option<Date>,
+    }
+
+    impl Default for Record {
+        fn default() -> Self {
+            Record {
+                posted: Date::MIN,
+                julian: Date::MIN,
+                stamp: PrimitiveDateTime::MIN,
+                closed: None,
+            }
+        }
+    }
+
+    #[test]
+    fn date_patterns() {
+        const EXAMPLE: &str = "202401312024060240131134500      ";
+        let unpacked = Record::from_ascii(EXAMPLE).unwrap();
+        let posted = Date::from_calendar_date(2024, Month::January, 31).unwrap();
+        assert_eq!(unpacked.posted, posted);
+        assert_eq!(unpacked.julian, Date::from_ordinal_date(2024, 60).unwrap());
+        assert_eq!(
+            unpacked.stamp,
+            PrimitiveDateTime::new(posted, Time::from_hms(13, 45, 0).unwrap())
+        );
+        assert_eq!(unpacked.closed, None);
+
+        // None packs as the blank character
+        assert_eq!(
+            unpacked.to_ascii().unwrap(),
+            "202401312024060240131134500000000"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use ascii_pack::{AsciiPack, AsciiPackError, Static};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Mixed {
+    #[pack(size = 4)]
+    pub id: u32,
+
+    #[pack(delimiter = "|")]
+    pub name: String,
+
+    #[pack(delimiter = "|", size = 5, pad_left = '0')]
+    pub amount: u32,
+
+    #[pack(size = 2)]
+    pub code: String,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+#[pack_struct(separator = ",", quote = '"')]
+pub struct Csv {
+    #[pack]
+    pub id: u32,
+
+    #[pack(size = 3, pad_left = ' ', trim)]
+    pub name: String,
+
+    #[pack_static(text = "X")]
+    pub marker: Static,
+
+    #[pack]
+    pub note: String,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+#[pack_struct(separator = ";", escape = '\\')]
+pub struct Escaped {
+    #[pack]
+    pub left: String,
+
+    #[pack]
+    pub right: String,
+}
+
+#[test]
+fn delimited_fields() {
+    const EXAMPLE: &str = "0001Jane Doe|00250|EN";
+    let unpacked = Mixed::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(unpacked.id, 1);
+    assert_eq!(unpacked.name, "Jane Doe");
+    assert_eq!(unpacked.amount, 250);
+    assert_eq!(unpacked.code, "EN");
+    assert_eq!(unpacked.to_ascii().unwrap(), EXAMPLE);
+    assert_eq!(Mixed::SIZE, None);
+
+    // an empty value is followed directly by its delimiter
+    let empty = Mixed::from_ascii("0002|00001|FR").unwrap();
+    assert_eq!(empty.name, "");
+    assert_eq!(empty.to_ascii().unwrap(), "0002|00001|FR");
+}
+
+#[test]
+fn separated_struct() {
+    let unpacked = Csv::from_ascii(r#"42,"a,b",X,"say ""hi""""#).unwrap();
+    assert_eq!(unpacked.id, 42);
+    assert_eq!(unpacked.name, "a,b");
+    assert_eq!(unpacked.note, r#"say "hi""#);
+    assert_eq!(Csv::SIZE, None);
+
+    // values are only quoted when they need to be
+    assert_eq!(unpacked.to_ascii().unwrap(), r#"42,"a,b",X,"say ""hi""""#);
+    let plain = Csv {
+        id: 7,
+        name: "ab".to_owned(),
+        marker: Static,
+        note: "note".to_owned(),
+    };
+    assert_eq!(plain.to_ascii().unwrap(), "7, ab,X,note");
+    assert_eq!(Csv::from_ascii("7, ab,X,note").unwrap(), plain);
+}
+
+#[test]
+fn escaped_values() {
+    let unpacked = Escaped::from_ascii(r"a\;b;c\\d").unwrap();
+    assert_eq!(unpacked.left, "a;b");
+    assert_eq!(unpacked.right, r"c\d");
+    assert_eq!(unpacked.to_ascii().unwrap(), r"a\;b;c\\d");
+}
+
+#[test]
+fn delimited_errors() {
+    // the delimiter is missing
+    let missing = Mixed::from_ascii("0001Jane Doe").unwrap_err();
+    assert!(matches!(missing.root_cause(), AsciiPackError::Unpack(_)));
+
+    // unterminated quotes
+    assert!(Csv::from_ascii(r#"1,"ab,X,note"#).is_err());
+
+    // the static column does not match
+    let mismatch = Csv::from_ascii("1,ab,Y,note").unwrap_err();
+    assert!(matches!(
+        mismatch.root_cause(),
+        AsciiPackError::StaticMismatch { .. }
+    ));
+
+    // a value containing the delimiter cannot be written without quotes
+    let unquoted = Mixed {
+        name: "a|b".to_owned(),
+        ..Default::default()
+    };
+    assert!(unquoted.to_ascii().is_err());
+}
@@ -0,0 +1,67 @@
+use ascii_pack::{AsciiPack, AsciiPackError};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Amount {
+    #[pack(size = 1)]
+    pub sign: char,
+
+    #[pack(size = 3)]
+    pub value: u32,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Monthly {
+    #[pack(size = 4)]
+    pub year: u32,
+
+    #[pack_vec(size = 3)]
+    pub amounts: [u32; 12],
+
+    #[pack_vec(size = 2, count = 3)]
+    pub codes: Vec<String>,
+
+    // the size of the items is taken from Amount::SIZE
+    #[pack_vec]
+    pub totals: [Amount; 2],
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Windowed<const N: usize> {
+    #[pack_vec(size = 2, count = { N })]
+    pub values: Vec<u8>,
+}
+
+const MONTHLY: &str = "2023001002003004005006007008009010011012AABBCC+100-200";
+
+#[test]
+fn fixed_count_unpack() {
+    let unpacked = Monthly::from_ascii(MONTHLY).unwrap();
+    assert_eq!(unpacked.year, 2023);
+    assert_eq!(unpacked.amounts, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+    assert_eq!(unpacked.codes, ["AA", "BB", "CC"]);
+    assert_eq!(unpacked.totals[1].sign, '-');
+    assert_eq!(unpacked.totals[1].value, 200);
+    assert_eq!(unpacked.to_ascii().unwrap(), MONTHLY);
+}
+
+#[test]
+fn fixed_count_size() {
+    assert_eq!(Monthly::SIZE, Some(MONTHLY.len()));
+    assert_eq!(Windowed::<4>::SIZE, Some(8));
+}
+
+#[test]
+fn fixed_count_wrong_length() {
+    let mut unpacked = Monthly::from_ascii(MONTHLY).unwrap();
+    unpacked.codes.pop();
+    assert!(matches!(unpacked.to_ascii(), Err(AsciiPackError::Pack(_))));
+
+    assert!(Monthly::from_ascii(&MONTHLY[..MONTHLY.len() - 4]).is_err());
+}
+
+#[test]
+fn fixed_count_const_generic() {
+    let unpacked = Windowed::<3>::from_ascii("010203").unwrap();
+    assert_eq!(unpacked.values, [1, 2, 3]);
+    assert_eq!(unpacked.to_ascii().unwrap(), "010203");
+}
@@ -0,0 +1,111 @@
+use std::marker::PhantomData;
+
+use ascii_pack::{until, AsciiPack, Static};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Header {
+    #[pack(size = 2)]
+    pub kind: String,
+
+    #[pack(size = 6)]
+    pub sequence: u32,
+}
+
+/// The same envelope wraps every kind of body
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Envelope<T> {
+    #[pack(size = 8)]
+    pub header: Header,
+
+    #[pack]
+    pub body: T,
+
+    #[pack_static(text = "\n")]
+    pub newline: Static,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Payment {
+    #[pack(size = 6)]
+    pub amount: u32,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Note {
+    #[pack(size = 5, pad_left = ' ')]
+    pub text: String,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Padded<const N: usize> {
+    #[pack(size = N, pad_left = ' ')]
+    pub value: String,
+
+    #[pack_vec(size = N, until = until::empty)]
+    pub rest: Vec<u32>,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Borrowed<'a, T>
+where
+    T: Default,
+{
+    #[pack(size = 3)]
+    pub value: T,
+
+    #[pack_ignore]
+    pub marker: PhantomData<&'a ()>,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+pub enum Either<L, R> {
+    #[pack_tag = "L"]
+    Left(#[pack(size = 4)] L),
+    #[pack_tag = "R"]
+    Right(#[pack(size = 4)] R),
+}
+
+#[test]
+fn generic_envelope() {
+    const PAYMENT: &str = "PA000001001250\n";
+    const NOTE: &str = "NO000002 TEXT\n";
+
+    let payment = Envelope::<Payment>::from_ascii(PAYMENT).unwrap();
+    assert_eq!(payment.header.sequence, 1);
+    assert_eq!(payment.body.amount, 1250);
+    assert_eq!(payment.to_ascii().unwrap(), PAYMENT);
+
+    let note = Envelope::<Note>::from_ascii(NOTE).unwrap();
+    assert_eq!(note.header.kind, "NO");
+    assert_eq!(note.body.text, " TEXT");
+    assert_eq!(note.to_ascii().unwrap(), NOTE);
+}
+
+#[test]
+fn const_generic_size() {
+    let three = Padded::<3>::from_ascii(" AB001002").unwrap();
+    assert_eq!(three.value, " AB");
+    assert_eq!(three.rest, vec![1, 2]);
+    assert_eq!(three.to_ascii().unwrap(), " AB001002");
+
+    let five = Padded::<5>::from_ascii("   AB00001").unwrap();
+    assert_eq!(five.value, "   AB");
+    assert_eq!(five.rest, vec![1]);
+    assert_eq!(five.to_ascii().unwrap(), "   AB00001");
+}
+
+#[test]
+fn lifetimes_and_where_clauses() {
+    let borrowed = Borrowed::<u8>::from_ascii("042").unwrap();
+    assert_eq!(borrowed.value, 42);
+    assert_eq!(borrowed.to_ascii().unwrap(), "042");
+}
+
+#[test]
+fn generic_enum() {
+    let left = Either::<u16, String>::from_ascii("L0012").unwrap();
+    assert_eq!(left, Either::Left(12));
+    let right = Either::<u16, String>::from_ascii("RABCD").unwrap();
+    assert_eq!(right, Either::Right("ABCD".to_owned()));
+    assert_eq!(right.to_ascii().unwrap(), "RABCD");
+}
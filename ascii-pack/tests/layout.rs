@@ -0,0 +1,127 @@
+use ascii_pack::{until, Align, AsciiPack, FieldKind, FieldLayout, Layout, Static};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Inner {
+    #[pack(size = 5, align = "left", pad = ' ')]
+    pub name: String,
+
+    #[pack(size = 4)]
+    pub number: u32,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Record {
+    #[pack_static(text = "HD")]
+    pub header: Static,
+
+    #[pack_ignore]
+    pub ignored: Option<u32>,
+
+    #[pack]
+    pub inner: Inner,
+
+    #[pack_vec(size = 2, until = until::empty)]
+    pub items: Vec<u32>,
+
+    #[pack(size = 3)]
+    pub trailer: u32,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+pub enum Message {
+    #[pack_tag = "A"]
+    Ack(#[pack(size = 2)] u32),
+    #[pack_tag = "IN"]
+    Info {
+        #[pack]
+        inner: Inner,
+    },
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub enum Status {
+    #[default]
+    #[pack(code = "OK")]
+    Ok,
+    #[pack(other)]
+    Other(String),
+}
+
+#[test]
+fn struct_layout() {
+    let layout = Record::layout();
+    let names: Vec<&str> = layout.iter().map(|field| field.name).collect();
+    assert_eq!(names, ["header", "ignored", "inner", "items", "trailer"]);
+
+    assert_eq!(
+        layout[0],
+        FieldLayout {
+            name: "header",
+            kind: FieldKind::PackStatic,
+            size: Some(2),
+            offset: Some(0),
+            align: None,
+            pad: None,
+            text: Some("HD"),
+            type_name: "Static",
+            nested: &[],
+        }
+    );
+
+    assert_eq!(layout[1].kind, FieldKind::PackIgnore);
+    assert_eq!(layout[1].size, Some(0));
+    assert_eq!(layout[1].offset, Some(2));
+    assert_eq!(layout[1].type_name, "Option<u32>");
+
+    assert_eq!(layout[2].kind, FieldKind::Pack);
+    assert_eq!(layout[2].size, Some(9));
+    assert_eq!(layout[2].offset, Some(2));
+    assert_eq!(layout[2].nested, Inner::layout());
+
+    assert_eq!(layout[3].kind, FieldKind::PackVec);
+    assert_eq!(layout[3].size, Some(2));
+    assert_eq!(layout[3].offset, Some(11));
+    assert_eq!(layout[3].type_name, "Vec<u32>");
+
+    // the offset after a variable length field is unknown
+    assert_eq!(layout[4].offset, None);
+    assert_eq!(layout[4].align, Some(Align::Right));
+    assert_eq!(layout[4].pad, Some('0'));
+}
+
+#[test]
+fn nested_layout() {
+    let inner = Inner::layout();
+    assert_eq!(inner[0].align, Some(Align::Left));
+    assert_eq!(inner[0].pad, Some(' '));
+    assert_eq!(inner[1].offset, Some(5));
+    assert_eq!(inner[1].type_name, "u32");
+    assert!(inner[1].nested.is_empty());
+}
+
+#[test]
+fn tagged_enum_layout() {
+    let layout = Message::layout();
+    assert_eq!(layout.len(), 2);
+
+    assert_eq!(layout[0].name, "Ack");
+    assert_eq!(layout[0].kind, FieldKind::Variant);
+    assert_eq!(layout[0].text, Some("A"));
+    assert_eq!(layout[0].size, Some(3));
+    assert_eq!(layout[0].nested[0].name, "0");
+    assert_eq!(layout[0].nested[0].offset, Some(1));
+
+    assert_eq!(layout[1].name, "Info");
+    assert_eq!(layout[1].size, Some(11));
+    assert_eq!(layout[1].nested[0].offset, Some(2));
+    assert_eq!(layout[1].nested[0].nested, Inner::layout());
+}
+
+#[test]
+fn code_enum_layout() {
+    let layout = Status::layout();
+    assert_eq!(layout[0].text, Some("OK"));
+    assert_eq!(layout[0].size, Some(2));
+    assert_eq!(layout[1].name, "Other");
+    assert_eq!(layout[1].size, None);
+}
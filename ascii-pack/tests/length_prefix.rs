@@ -0,0 +1,150 @@
+use ascii_pack::{AsciiPack, AsciiPackError};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Inner {
+    #[pack(size = 2)]
+    pub id: u8,
+
+    #[pack(len_prefix = 2)]
+    pub label: String,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Prefixed {
+    #[pack(len_prefix = 3)]
+    pub name: String,
+
+    #[pack(len_prefix = 1)]
+    pub number: u64,
+
+    #[pack(len_prefix = 2)]
+    pub inner: Inner,
+
+    #[pack(size = 2)]
+    pub trailer: String,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct SizedFrom {
+    #[pack(size = 2)]
+    pub name_len: u8,
+
+    #[pack(size = 4)]
+    pub other: u32,
+
+    #[pack(size_from = name_len)]
+    pub name: String,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct FormattedFrom {
+    #[pack(size = 2)]
+    pub flag_len: u8,
+
+    #[pack(size = 2)]
+    pub amount_len: u8,
+
+    #[pack(size = 2)]
+    pub code_len: u8,
+
+    #[pack(size_from = flag_len, bool = ("YES", "NO"))]
+    pub flag: bool,
+
+    #[pack(size_from = amount_len, sign = "leading", plus)]
+    pub amount: i32,
+
+    #[pack(size_from = code_len, radix = 16)]
+    pub code: Option<u32>,
+}
+
+#[test]
+fn len_prefix() {
+    const EXAMPLE: &str = "005HELLO512345070103ABCEN";
+    let unpacked = Prefixed::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(unpacked.name, "HELLO");
+    assert_eq!(unpacked.number, 12345);
+    assert_eq!(unpacked.inner.id, 1);
+    assert_eq!(unpacked.inner.label, "ABC");
+    assert_eq!(unpacked.trailer, "EN");
+    assert_eq!(Prefixed::SIZE, None);
+
+    let packed = Prefixed {
+        name: "HELLO".to_owned(),
+        number: 12345,
+        inner: Inner {
+            id: 1,
+            label: "ABC".to_owned(),
+        },
+        trailer: "ND".to_owned(),
+    };
+    assert_eq!(packed.to_ascii().unwrap(), "005HELLO512345070103ABCND");
+}
+
+#[test]
+fn len_prefix_errors() {
+    // the prefix is not a number
+    assert!(Prefixed::from_ascii("0x5HELLO512345").is_err());
+    // the value is shorter than its prefix
+    assert!(Prefixed::from_ascii("009HELLO").is_err());
+
+    let overflow = Prefixed {
+        number: 1234567890,
+        ..Default::default()
+    };
+    match overflow.to_ascii() {
+        Err(AsciiPackError::Pack(message)) => assert!(message.contains("number")),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn size_from() {
+    const EXAMPLE: &str = "050042HELLO";
+    let unpacked = SizedFrom::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(unpacked.name, "HELLO");
+    assert_eq!(unpacked.other, 42);
+
+    // the length is written from the value
+    let packed = SizedFrom {
+        name_len: 0,
+        other: 42,
+        name: "HI".to_owned(),
+    };
+    assert_eq!(packed.to_ascii().unwrap(), "020042HI");
+
+    // the length field rejects values which overflow its size
+    let overflow = SizedFrom {
+        name: "X".repeat(100),
+        ..Default::default()
+    };
+    assert!(overflow.to_ascii().is_err());
+}
+
+#[test]
+fn size_from_formatted() {
+    // the lengths are those of the formatted text
+    let packed = FormattedFrom {
+        flag: true,
+        amount: 12,
+        code: Some(255),
+        ..Default::default()
+    };
+    const EXPECTED: &str = "030302YES+12ff";
+    assert_eq!(packed.to_ascii().unwrap(), EXPECTED);
+    let unpacked = FormattedFrom::from_ascii(EXPECTED).unwrap();
+    assert_eq!(
+        (unpacked.flag, unpacked.amount, unpacked.code),
+        (true, 12, Some(255))
+    );
+
+    let empty = FormattedFrom {
+        amount: -7,
+        ..Default::default()
+    };
+    assert_eq!(empty.to_ascii().unwrap(), "020200NO-7");
+    let unpacked = FormattedFrom::from_ascii("020200NO-7").unwrap();
+    assert_eq!(
+        (unpacked.flag, unpacked.amount, unpacked.code),
+        (false, -7, None)
+    );
+}
@@ -1,5 +1,4 @@
 use ascii_pack::AsciiPack;
-use ascii_pack::AsciiPackError;
 
 #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
 pub struct Inner {
@@ -0,0 +1,82 @@
+use ascii_pack::AsciiPack;
+
+#[derive(AsciiPack, PartialEq, Debug, Default)]
+pub struct Formatted {
+    #[pack(size = 8, precision = 2)]
+    pub rate: f64,
+
+    #[pack(size = 8, precision = 3, decimal = ',')]
+    pub european: f64,
+
+    #[pack(size = 12, precision = 2, grouping = ',', pad_left = ' ', trim)]
+    pub total: f64,
+
+    #[pack(
+        size = 10,
+        decimal = ',',
+        grouping = '.',
+        sign = "leading",
+        pad_left = ' ',
+        trim
+    )]
+    pub balance: i64,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Encoded {
+    #[pack(size = 4, radix = 16)]
+    pub lower: u16,
+
+    #[pack(size = 4, radix = 16, case = "upper")]
+    pub upper: u16,
+
+    #[pack(size = 3, radix = 8)]
+    pub mode: u32,
+
+    #[pack(size = 8, radix = 2)]
+    pub flags: u8,
+
+    #[pack(size = 4, radix = 16, sign = "leading")]
+    pub offset: i16,
+}
+
+#[test]
+fn decimal_formatting() {
+    let packed = Formatted {
+        rate: 1.5,
+        european: 3.25,
+        total: 1234567.891,
+        balance: -1234567,
+    };
+    const EXPECTED: &str = "00001.500003,2501,234,567.89-1.234.567";
+    assert_eq!(packed.to_ascii().unwrap(), EXPECTED);
+
+    let unpacked = Formatted::from_ascii(EXPECTED).unwrap();
+    assert_eq!(unpacked.rate, 1.5);
+    assert_eq!(unpacked.european, 3.25);
+    assert_eq!(unpacked.total, 1234567.89);
+    assert_eq!(unpacked.balance, -1234567);
+    assert_eq!(unpacked.to_ascii().unwrap(), EXPECTED);
+}
+
+#[test]
+fn radix_formatting() {
+    let packed = Encoded {
+        lower: 0xbeef,
+        upper: 0x2a,
+        mode: 0o755,
+        flags: 0b1010,
+        offset: -0x1f,
+    };
+    const EXPECTED: &str = "beef002A75500001010-01f";
+    assert_eq!(packed.to_ascii().unwrap(), EXPECTED);
+    assert_eq!(Encoded::from_ascii(EXPECTED).unwrap(), packed);
+
+    // letter digits of either case are accepted
+    let mixed = Encoded::from_ascii("BEEF002a75500001010-01F").unwrap();
+    assert_eq!(mixed, packed);
+
+    // digits outside the radix are rejected
+    assert!(Encoded::from_ascii("beeg002A75500001010-01F").is_err());
+    assert!(Encoded::from_ascii("beef002A75800001010-01F").is_err());
+}
@@ -0,0 +1,100 @@
+use ascii_pack::{AsciiPack, AsciiPackError};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Record {
+    #[pack(size = 8)]
+    pub date: Option<u32>,
+
+    #[pack(size = 4, blank = '0')]
+    pub count: Option<u16>,
+
+    #[pack(size = 5, pad_left = ' ', blank = '*')]
+    pub code: Option<String>,
+
+    #[pack(size = 2)]
+    pub kind: String,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Inner {
+    #[pack(size = 2)]
+    pub id: u8,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Variable {
+    #[pack(len_prefix = 2)]
+    pub note: Option<String>,
+
+    #[pack(delimiter = "|")]
+    pub name: Option<String>,
+
+    #[pack(size = 2)]
+    pub inner: Option<Inner>,
+}
+
+#[test]
+fn blank_is_none() {
+    const EXAMPLE: &str = "        0000*****AB";
+    let unpacked = Record::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(unpacked.date, None);
+    assert_eq!(unpacked.count, None);
+    assert_eq!(unpacked.code, None);
+    assert_eq!(unpacked.kind, "AB");
+    assert_eq!(unpacked.to_ascii().unwrap(), EXAMPLE);
+    assert_eq!(Record::SIZE, Some(19));
+}
+
+#[test]
+fn present_values() {
+    const EXAMPLE: &str = "202401310012  XYZCD";
+    let unpacked = Record::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(unpacked.date, Some(20240131));
+    assert_eq!(unpacked.count, Some(12));
+    assert_eq!(unpacked.code.as_deref(), Some("  XYZ"));
+    assert_eq!(unpacked.to_ascii().unwrap(), EXAMPLE);
+
+    // values which are only partly blank are still unpacked
+    assert!(Record::from_ascii("    0131000  XYZCD").is_err());
+}
+
+#[test]
+fn unsized_options() {
+    const EXAMPLE: &str = "00|07";
+    let unpacked = Variable::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(unpacked.note, None);
+    assert_eq!(unpacked.name, None);
+    assert_eq!(unpacked.inner, Some(Inner { id: 7 }));
+    assert_eq!(unpacked.to_ascii().unwrap(), EXAMPLE);
+
+    let packed = Variable {
+        note: Some("HI".to_owned()),
+        name: Some("JO".to_owned()),
+        inner: None,
+    };
+    assert_eq!(packed.to_ascii().unwrap(), "02HIJO|  ");
+    assert_eq!(Variable::from_ascii("02HIJO|  ").unwrap(), packed);
+}
+
+#[test]
+fn blank_some_is_an_error() {
+    let zero = Record {
+        count: Some(0),
+        kind: "AB".to_owned(),
+        ..Default::default()
+    };
+    let error = zero.to_ascii().unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("count packs as '0000', which is blank"),
+        "{}",
+        error
+    );
+
+    let empty = Variable {
+        name: Some(String::new()),
+        ..Default::default()
+    };
+    assert!(matches!(empty.to_ascii(), Err(AsciiPackError::Pack(_))));
+}
@@ -1,6 +1,5 @@
 use ascii_pack::until;
 use ascii_pack::AsciiPack;
-use ascii_pack::AsciiPackError;
 
 const TEST_ASCII: &str = "  EXAMPLETESTTESTTEST00120654012346543345delimeterabc";
 
@@ -0,0 +1,90 @@
+use ascii_pack::{until, AsciiPack, AsciiPackError, AsciiPackReport, PathSegment, Static};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Record {
+    #[pack(size = 4)]
+    pub number: u32,
+
+    #[pack(size = 3)]
+    pub code: String,
+
+    #[pack_static(text = "//")]
+    pub delimeter: Static,
+
+    #[pack(size = 2)]
+    pub count: u8,
+
+    #[pack_vec(size = 2, until = until::empty)]
+    pub items: Vec<u8>,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Unsized {
+    #[pack(size = 2)]
+    pub count: u8,
+
+    #[pack_vec(size = 2, until = until::starts_with("|"))]
+    pub items: Vec<u8>,
+
+    #[pack_static(text = "|")]
+    pub end: Static,
+
+    #[pack(size = 2)]
+    pub after: u8,
+}
+
+#[test]
+fn valid_record() {
+    let (record, diagnostics) = Record::from_ascii_report("0012ABC//020102");
+    assert!(diagnostics.is_empty());
+    assert_eq!(record, Record::from_ascii("0012ABC//020102").unwrap());
+}
+
+#[test]
+fn every_fixed_field_reported() {
+    let (record, diagnostics) = Record::from_ascii_report("00x2ABC--0201xx");
+
+    let fields: Vec<_> = diagnostics.iter().map(|d| (d.field, d.offset)).collect();
+    assert_eq!(
+        fields,
+        [
+            (Some("number"), 0),
+            (Some("delimeter"), 7),
+            (Some("items"), 11)
+        ]
+    );
+
+    // the fields which did unpack are populated
+    assert_eq!(record.number, 0);
+    assert_eq!(record.code, "ABC");
+    assert_eq!(record.count, 2);
+    assert_eq!(record.items, [1]);
+
+    match &diagnostics[2].error {
+        AsciiPackError::Context { path, text, .. } => {
+            assert_eq!(path, &[PathSegment::Field("items"), PathSegment::Index(1)]);
+            assert_eq!(text, "xx");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn stops_at_unsized_field() {
+    let diagnostics = Unsized::validate_ascii("xx01x2|yy");
+    let fields: Vec<_> = diagnostics.iter().map(|d| (d.field, d.offset)).collect();
+    assert_eq!(fields, [(Some("count"), 0), (Some("items"), 2)]);
+}
+
+#[test]
+fn trailing_reported() {
+    let (record, diagnostics) = Unsized::from_ascii_report("0101|02xyz");
+    assert_eq!(record.after, 2);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].field, None);
+    assert_eq!(diagnostics[0].offset, 7);
+    assert!(matches!(
+        diagnostics[0].error,
+        AsciiPackError::TrailingInput { .. }
+    ));
+}
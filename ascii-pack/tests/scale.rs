@@ -0,0 +1,90 @@
+use ascii_pack::{AsciiPack, AsciiPackError};
+
+#[derive(AsciiPack, PartialEq, Debug, Default)]
+pub struct Amounts {
+    #[pack(size = 9, scale = 2)]
+    pub amount: f64,
+
+    #[pack(size = 6, scale = 2)]
+    pub whole: u32,
+
+    #[pack(size = 6, scale = 3, sign = "overpunch")]
+    pub rate: f64,
+
+    #[pack(size = 5, scale = 1, blank = ' ')]
+    pub limit: Option<i32>,
+}
+
+#[test]
+fn implied_decimals() {
+    const EXAMPLE: &str = "00001234501230000125J     ";
+    let unpacked = Amounts::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(unpacked.amount, 123.45);
+    assert_eq!(unpacked.whole, 123);
+    assert_eq!(unpacked.rate, -1.251);
+    assert_eq!(unpacked.limit, None);
+    assert_eq!(unpacked.to_ascii().unwrap(), EXAMPLE);
+
+    let packed = Amounts {
+        amount: 0.5,
+        whole: 7,
+        rate: 0.25,
+        limit: Some(3),
+    };
+    assert_eq!(packed.to_ascii().unwrap(), "00000005000070000025000030");
+    assert_eq!(
+        Amounts::from_ascii("00000005000070000025000030").unwrap(),
+        packed
+    );
+}
+
+#[test]
+fn scale_errors() {
+    // more fractional digits than the scale allows
+    let precise = Amounts {
+        amount: 1.234,
+        ..Default::default()
+    };
+    match precise.to_ascii() {
+        Err(AsciiPackError::Pack(message)) => assert!(message.contains("fractional digits")),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // negative amounts need a sign option to be padded with zeros
+    let negative = Amounts {
+        amount: -5.0,
+        ..Default::default()
+    };
+    match negative.to_ascii() {
+        Err(AsciiPackError::Pack(message)) => assert!(message.contains("requires a sign")),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // integers cannot hold fractional digits
+    let error = Amounts::from_ascii("00001234501230100125J     ").unwrap_err();
+    assert!(matches!(error.root_cause(), AsciiPackError::Unpack(_)));
+
+    // only digits can have an implied decimal point
+    assert!(Amounts::from_ascii("0000123.501230000125J     ").is_err());
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn rust_decimal() {
+    use rust_decimal::Decimal;
+
+    #[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+    pub struct Price {
+        #[pack(size = 9, scale = 2, sign = "leading")]
+        pub price: Decimal,
+    }
+
+    let unpacked = Price::from_ascii("-00012345").unwrap();
+    assert_eq!(unpacked.price, Decimal::new(-12345, 2));
+    assert_eq!(unpacked.to_ascii().unwrap(), "-00012345");
+
+    let precise = Price {
+        price: Decimal::new(1005, 3),
+    };
+    assert!(precise.to_ascii().is_err());
+}
@@ -0,0 +1,148 @@
+use ascii_pack::{AsciiPack, AsciiPackError};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Signed {
+    #[pack(size = 5, sign = "leading")]
+    pub leading: i32,
+
+    #[pack(size = 5, sign = "trailing")]
+    pub trailing: i32,
+
+    #[pack(size = 5, sign = "leading", plus)]
+    pub plus: i32,
+
+    #[pack(size = 4, sign = "overpunch")]
+    pub overpunch: i64,
+
+    #[pack(size = 4, sign = "overpunch", plus)]
+    pub overpunch_plus: i64,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Spaced {
+    #[pack(size = 6, sign = "leading", pad_left = ' ', trim)]
+    pub amount: i16,
+
+    #[pack(size = 6, sign = "trailing", blank = ' ')]
+    pub balance: Option<i32>,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Unsigned {
+    #[pack(size = 5)]
+    pub plain: i32,
+
+    #[pack(size = 4, radix = 16)]
+    pub hex: i32,
+
+    #[pack(size = 6, pad_left = ' ', trim)]
+    pub spaced: i32,
+}
+
+#[test]
+fn negative_numbers() {
+    let packed = Signed {
+        leading: -12,
+        trailing: -12,
+        plus: -12,
+        overpunch: -12,
+        overpunch_plus: -120,
+    };
+    const EXPECTED: &str = "-00120012--0012001K012}";
+    assert_eq!(packed.to_ascii().unwrap(), EXPECTED);
+    assert_eq!(Signed::from_ascii(EXPECTED).unwrap(), packed);
+}
+
+#[test]
+fn positive_numbers() {
+    let packed = Signed {
+        leading: 12,
+        trailing: 12,
+        plus: 12,
+        overpunch: 12,
+        overpunch_plus: 120,
+    };
+    const EXPECTED: &str = "0001200012+00120012012{";
+    assert_eq!(packed.to_ascii().unwrap(), EXPECTED);
+    assert_eq!(Signed::from_ascii(EXPECTED).unwrap(), packed);
+
+    // explicit plus signs and positive overpunch letters are always accepted
+    let unpacked = Signed::from_ascii("+00120012+000120012001B").unwrap();
+    assert_eq!(unpacked.leading, 12);
+    assert_eq!(unpacked.trailing, 12);
+    assert_eq!(unpacked.plus, 12);
+    assert_eq!(unpacked.overpunch, 12);
+    assert_eq!(unpacked.overpunch_plus, 12);
+}
+
+#[test]
+fn padded_signs() {
+    let packed = Spaced {
+        amount: -42,
+        balance: Some(-7),
+    };
+    assert_eq!(packed.to_ascii().unwrap(), "-   4200007-");
+    assert_eq!(Spaced::from_ascii("-   4200007-").unwrap(), packed);
+
+    let blank = Spaced::from_ascii("    42      ").unwrap();
+    assert_eq!(blank.amount, 42);
+    assert_eq!(blank.balance, None);
+}
+
+#[test]
+fn sign_errors() {
+    // the sign does not fit in the field
+    let overflow = Signed {
+        leading: -12345,
+        ..Default::default()
+    };
+    assert!(overflow.to_ascii().is_err());
+
+    // unknown overpunch letter
+    let error = Signed::from_ascii("000120012+00120001Z001").unwrap_err();
+    match error.root_cause() {
+        AsciiPackError::Unpack(message) => assert_eq!(
+            message,
+            "'001Z' does not end with a digit or an overpunched digit"
+        ),
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[test]
+fn negative_without_sign() {
+    // spaces can be padded before the minus sign
+    let spaced = Unsigned {
+        spaced: -12,
+        ..Default::default()
+    };
+    assert_eq!(spaced.to_ascii().unwrap(), "000000000   -12");
+    assert_eq!(Unsigned::from_ascii("000000000   -12").unwrap(), spaced);
+
+    // while zeros cannot
+    for negative in [
+        Unsigned {
+            plain: -12,
+            ..Default::default()
+        },
+        Unsigned {
+            hex: -255,
+            ..Default::default()
+        },
+    ] {
+        match negative.to_ascii() {
+            Err(AsciiPackError::Pack(message)) => {
+                assert!(message.contains("requires a sign option"), "{message}")
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    // values filling the field need no padding
+    let full = Unsigned {
+        plain: -1234,
+        ..Default::default()
+    };
+    assert_eq!(full.to_ascii().unwrap(), "-12340000     0");
+    assert_eq!(Unsigned::from_ascii("-12340000     0").unwrap(), full);
+}
@@ -0,0 +1,88 @@
+use ascii_pack::{until, AsciiPack, Static};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Inner {
+    #[pack(size = 5, pad_left = ' ')]
+    pub my_string: String,
+
+    #[pack(size = 4)]
+    pub my_number: usize,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Outer {
+    #[pack(size = 4)]
+    pub field1: u32,
+
+    #[pack_ignore]
+    pub ignored: Option<usize>,
+
+    // the size is taken from Inner::SIZE
+    #[pack]
+    pub inner: Inner,
+
+    #[pack(size = 9)]
+    pub checked: Inner,
+
+    #[pack_static(text = "\r\n")]
+    pub line_ending: Static,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Unsized {
+    #[pack(size = 2)]
+    pub field1: u32,
+
+    #[pack_vec(size = 2, until = until::empty)]
+    pub items: Vec<u32>,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Padded<const N: usize> {
+    #[pack(size = N)]
+    pub value: u32,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+pub enum SameWidth {
+    #[pack_tag = "A"]
+    A(#[pack(size = 4)] u32),
+    #[pack_tag = "BB"]
+    B(#[pack(size = 3)] u32),
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+pub enum MixedWidth {
+    #[pack_tag = "A"]
+    A(#[pack(size = 4)] u32),
+    #[pack_tag = "B"]
+    B,
+}
+
+#[test]
+fn struct_size() {
+    assert_eq!(Inner::SIZE, Some(9));
+    assert_eq!(Outer::SIZE, Some(24));
+    assert_eq!(Unsized::SIZE, None);
+    assert_eq!(Padded::<7>::SIZE, Some(7));
+    assert_eq!(<String as AsciiPack>::SIZE, None);
+}
+
+#[test]
+fn enum_size() {
+    assert_eq!(SameWidth::SIZE, Some(5));
+    assert_eq!(MixedWidth::SIZE, None);
+}
+
+#[test]
+fn omitted_nested_size() {
+    const EXAMPLE: &str = "0123 ABCD0001EFGHI0002\r\n";
+
+    let outer = Outer::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(outer.field1, 123);
+    assert_eq!(outer.inner.my_string, " ABCD");
+    assert_eq!(outer.inner.my_number, 1);
+    assert_eq!(outer.checked.my_string, "EFGHI");
+    assert_eq!(outer.checked.my_number, 2);
+    assert_eq!(outer.to_ascii().unwrap(), EXAMPLE);
+}
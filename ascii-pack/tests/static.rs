@@ -1,4 +1,4 @@
-use ascii_pack::{AsciiPack, AsciiPackError, Static};
+use ascii_pack::{AsciiPack, AsciiPackError, AsciiPackUnsized, Static};
 
 const EXAMPLE: &str = "BEGIN1234END";
 
@@ -20,3 +20,45 @@ fn static_delimeters() {
     assert_eq!(packed.number, 1234);
     assert_eq!(packed.to_ascii().unwrap(), EXAMPLE);
 }
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct LenientTest {
+    #[pack_static(text = "BEGIN", lenient)]
+    pub begin: Static,
+
+    #[pack(size = 4)]
+    pub number: usize,
+}
+
+#[test]
+fn static_mismatch() {
+    let error = StaticTest::from_ascii("BEGXN1234END").unwrap_err();
+    match error.root_cause() {
+        AsciiPackError::StaticMismatch {
+            expected,
+            found,
+            offset,
+        } => {
+            assert_eq!(*expected, "BEGIN");
+            assert_eq!(found, "BEGXN");
+            assert_eq!(*offset, 0);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    let error = StaticTest::from_ascii_unsized("BEGIN1234EN").unwrap_err();
+    match error.root_cause() {
+        AsciiPackError::StaticMismatch { found, offset, .. } => {
+            assert_eq!(found, "EN");
+            assert_eq!(*offset, 9);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn static_lenient() {
+    let packed = LenientTest::from_ascii("BEGXN1234").unwrap();
+    assert_eq!(packed.number, 1234);
+    assert_eq!(packed.to_ascii().unwrap(), "BEGIN1234");
+}
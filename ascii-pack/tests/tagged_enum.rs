@@ -0,0 +1,96 @@
+use ascii_pack::{until, AsciiPack, Static};
+
+/// `01` header, `02` detail and `99` trailer records
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+#[pack_enum(tag_size = 2)]
+pub enum Record {
+    #[pack_tag = "01"]
+    Header {
+        #[pack(size = 8)]
+        date: u32,
+
+        #[pack(size = 6, pad_left = ' ')]
+        sender: String,
+    },
+
+    #[pack_tag = "02"]
+    Detail(
+        #[pack(size = 4)] usize,
+        #[pack(size = 5, pad_left = ' ')] String,
+    ),
+
+    #[default]
+    #[pack_tag = "99"]
+    Trailer,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+pub enum Segment {
+    #[pack_tag = "A"]
+    Short {
+        #[pack(size = 2)]
+        value: u8,
+    },
+
+    #[pack_tag = "AB"]
+    Long {
+        #[pack(size = 4)]
+        value: u16,
+    },
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Batch {
+    #[pack_static(text = "BATCH")]
+    pub begin: Static,
+
+    #[pack_vec(until = until::empty)]
+    pub records: Vec<Record>,
+}
+
+#[test]
+fn tagged_enum() {
+    let header = Record::from_ascii("0120231020  ACME").unwrap();
+    assert_eq!(
+        header,
+        Record::Header {
+            date: 20231020,
+            sender: "  ACME".to_owned()
+        }
+    );
+    assert_eq!(header.to_ascii().unwrap(), "0120231020  ACME");
+
+    let detail = Record::from_ascii("020042  ABC").unwrap();
+    assert_eq!(detail, Record::Detail(42, "  ABC".to_owned()));
+    assert_eq!(detail.to_ascii().unwrap(), "020042  ABC");
+
+    assert_eq!(Record::from_ascii("99").unwrap(), Record::Trailer);
+    assert_eq!(Record::Trailer.to_ascii().unwrap(), "99");
+
+    assert!(Record::from_ascii("55").is_err());
+    assert!(Record::from_ascii("").is_err());
+}
+
+#[test]
+fn tagged_enum_longest_prefix() {
+    assert_eq!(
+        Segment::from_ascii("AB1234").unwrap(),
+        Segment::Long { value: 1234 }
+    );
+    assert_eq!(
+        Segment::from_ascii("A12").unwrap(),
+        Segment::Short { value: 12 }
+    );
+}
+
+#[test]
+fn tagged_enum_records() {
+    const EXAMPLE: &str = "BATCH0120231020  ACME020001  ONE020002  TWO99";
+
+    let batch = Batch::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(batch.records.len(), 4);
+    assert_eq!(batch.records[2], Record::Detail(2, "  TWO".to_owned()));
+    assert_eq!(batch.records[3], Record::Trailer);
+
+    assert_eq!(batch.to_ascii().unwrap(), EXAMPLE);
+}
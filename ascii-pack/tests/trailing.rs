@@ -0,0 +1,109 @@
+use ascii_pack::{AsciiPack, AsciiPackError, AsciiPackUnsized};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Strict {
+    #[pack(size = 4)]
+    pub number: u32,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+#[pack_struct(trailing = "ignore")]
+pub struct Ignored {
+    #[pack(size = 4)]
+    pub number: u32,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+#[pack_struct(trailing = "whitespace")]
+pub struct Whitespace {
+    #[pack(size = 4)]
+    pub number: u32,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+#[pack_enum(tag_size = 2)]
+pub enum Tagged {
+    #[pack_tag = "01"]
+    A {
+        #[pack(size = 3)]
+        x: u16,
+    },
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+#[pack_enum(tag_size = 2, trailing = "whitespace")]
+pub enum TaggedWhitespace {
+    #[pack_tag = "01"]
+    A {
+        #[pack(size = 3)]
+        x: u16,
+    },
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+#[pack_enum(untagged, trailing = "ignore")]
+pub enum UntaggedIgnored {
+    Short(#[pack(size = 2)] u8),
+    Long(#[pack(size = 4)] u16),
+}
+
+#[test]
+fn trailing_error() {
+    assert_eq!(Strict::from_ascii("0012").unwrap().number, 12);
+    match Strict::from_ascii("0012 stray") {
+        Err(AsciiPackError::TrailingInput { remaining, offset }) => {
+            assert_eq!(remaining, 6);
+            assert_eq!(offset, 4);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // nested use still reports the consumed length
+    let (unpacked, consumed) = Strict::from_ascii_unsized("0012 stray").unwrap();
+    assert_eq!(unpacked.number, 12);
+    assert_eq!(consumed, 4);
+}
+
+#[test]
+fn trailing_ignore() {
+    assert_eq!(Ignored::from_ascii("0012 stray").unwrap().number, 12);
+}
+
+#[test]
+fn trailing_whitespace() {
+    assert_eq!(Whitespace::from_ascii("0012 \r\n").unwrap().number, 12);
+    match Whitespace::from_ascii("0012  x ") {
+        Err(AsciiPackError::TrailingInput { remaining, offset }) => {
+            assert_eq!(remaining, 2);
+            assert_eq!(offset, 6);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn trailing_enums() {
+    assert_eq!(Tagged::from_ascii("01123").unwrap(), Tagged::A { x: 123 });
+    match Tagged::from_ascii("01123GARBAGE") {
+        Err(AsciiPackError::TrailingInput { remaining, offset }) => {
+            assert_eq!(remaining, 7);
+            assert_eq!(offset, 5);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    let (unpacked, consumed) = Tagged::from_ascii_unsized("01123GARBAGE").unwrap();
+    assert_eq!(unpacked, Tagged::A { x: 123 });
+    assert_eq!(consumed, 5);
+
+    assert_eq!(
+        TaggedWhitespace::from_ascii("01123  ").unwrap(),
+        TaggedWhitespace::A { x: 123 }
+    );
+    assert!(TaggedWhitespace::from_ascii("01123 x").is_err());
+
+    // the first variant which unpacks is chosen, whatever follows it
+    assert_eq!(
+        UntaggedIgnored::from_ascii("1234").unwrap(),
+        UntaggedIgnored::Short(12)
+    );
+}
@@ -0,0 +1,127 @@
+// unpacking must report malformed input as an error rather than
+// panicking, no matter where the input is cut short or corrupted.
+
+use ascii_pack::{until, AsciiPack, AsciiPackError, AsciiPackUnsized, Static};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Inner {
+    #[pack(size = 5, pad_left = ' ')]
+    pub name: String,
+
+    #[pack(size = 4)]
+    pub number: usize,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Sized {
+    #[pack_static(text = "HD")]
+    pub header: Static,
+
+    #[pack]
+    pub inner: Inner,
+
+    #[pack_vec(size = 4, until = until::empty)]
+    pub items: Vec<u32>,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Item {
+    #[pack(size = 4)]
+    pub id: usize,
+
+    #[pack_static(text = "//")]
+    pub delimeter: Static,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Unsized {
+    #[pack_vec(until = until::starts_with("\n"))]
+    pub items: Vec<Item>,
+
+    #[pack(size = 1)]
+    pub newline: char,
+
+    #[pack_vec(size = 2, until = until::ascii_alpha)]
+    pub numbers: Vec<u8>,
+
+    #[pack(size = 3)]
+    pub trailer: String,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+pub enum Tagged {
+    #[pack_tag = "A"]
+    Short(#[pack(size = 2)] u32),
+    #[pack_tag = "BB"]
+    Long {
+        #[pack]
+        inner: Inner,
+    },
+}
+
+const SIZED: &str = "HD  ABC0123000100020003";
+const UNSIZED: &str = "0001//0002//\n0102END";
+const TAGGED: &str = "BB  ABC0123";
+
+/// Every prefix of `valid`, along with `valid` with each character
+/// replaced by a multibyte character.
+fn malformed(valid: &str) -> Vec<String> {
+    let mut inputs: Vec<String> = (0..valid.len())
+        .map(|end| valid[..end].to_owned())
+        .collect();
+    for (index, _) in valid.char_indices() {
+        let mut corrupted = valid.to_owned();
+        corrupted.replace_range(index..index + 1, "é");
+        inputs.push(corrupted);
+    }
+    inputs
+}
+
+#[test]
+fn truncated_input() {
+    assert!(Sized::from_ascii(SIZED).is_ok());
+    assert!(Unsized::from_ascii(UNSIZED).is_ok());
+    assert!(Tagged::from_ascii(TAGGED).is_ok());
+
+    for input in malformed(SIZED) {
+        let _ = Sized::from_ascii(&input);
+        let _ = Sized::from_ascii_unsized(&input);
+    }
+    for input in malformed(UNSIZED) {
+        let _ = Unsized::from_ascii(&input);
+        let _ = Unsized::from_ascii_unsized(&input);
+    }
+    for input in malformed(TAGGED) {
+        let _ = Tagged::from_ascii(&input);
+        let _ = Tagged::from_ascii_unsized(&input);
+    }
+}
+
+#[test]
+fn truncated_error() {
+    let error = Inner::from_ascii("  ABC01").unwrap_err();
+    match error.root_cause() {
+        AsciiPackError::Truncated {
+            field,
+            offset,
+            size,
+            available,
+        } => {
+            assert_eq!(*field, "number");
+            assert_eq!(*offset, 5);
+            assert_eq!(*size, 4);
+            assert_eq!(*available, 2);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    let error = Inner::from_ascii("  ABé123").unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        AsciiPackError::CharBoundary {
+            field: "name",
+            offset: 0
+        }
+    ));
+    assert!(Inner::from_ascii("").is_err());
+}
@@ -0,0 +1,86 @@
+use ascii_pack::{AsciiPack, AsciiPackReport, Static};
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+#[pack_struct(transparent)]
+pub struct AccountNo(String);
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+#[pack_struct(transparent)]
+pub struct Balance {
+    pub cents: u64,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Account(
+    #[pack(size = 8, pad_left = ' ')] AccountNo,
+    #[pack_static(text = "/")] Static,
+    #[pack(size = 10)] Balance,
+);
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Statement {
+    #[pack]
+    pub account: Account,
+
+    #[pack(size = 4)]
+    pub year: u16,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+#[pack_struct(transparent)]
+pub struct Wrapped(Account);
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct WrappedStatement {
+    #[pack]
+    pub account: Wrapped,
+
+    #[pack_vec(until = ascii_pack::until::empty)]
+    pub history: Vec<Wrapped>,
+}
+
+#[test]
+fn tuple_struct() {
+    const EXAMPLE: &str = "  AB1234/0000012345";
+
+    let account = Account::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(account.0, AccountNo("  AB1234".to_owned()));
+    assert_eq!(account.2, Balance { cents: 12345 });
+    assert_eq!(account.to_ascii().unwrap(), EXAMPLE);
+}
+
+#[test]
+fn transparent_newtype() {
+    assert_eq!(
+        AccountNo::from_ascii("AB1234").unwrap(),
+        AccountNo("AB1234".to_owned())
+    );
+    assert_eq!(AccountNo("AB1234".to_owned()).to_ascii().unwrap(), "AB1234");
+    assert!(Balance::from_ascii("12A").is_err());
+}
+
+#[test]
+fn nested_tuple_struct() {
+    const EXAMPLE: &str = "CD987654/00000000012023";
+
+    let statement = Statement::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(statement.account.0, AccountNo("CD987654".to_owned()));
+    assert_eq!(statement.account.2.cents, 1);
+    assert_eq!(statement.year, 2023);
+    assert_eq!(statement.to_ascii().unwrap(), EXAMPLE);
+}
+
+#[test]
+fn nested_transparent_newtype() {
+    const EXAMPLE: &str = "CD987654/0000000001  AB1234/0000012345";
+
+    let statement = WrappedStatement::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(statement.account.0 .0, AccountNo("CD987654".to_owned()));
+    assert_eq!(statement.history.len(), 1);
+    assert_eq!(statement.history[0].0 .2.cents, 12345);
+    assert_eq!(statement.to_ascii().unwrap(), EXAMPLE);
+
+    let (wrapped, diagnostics) = Wrapped::from_ascii_report("  AB1234/00000000x5");
+    assert_eq!(wrapped.0 .0, AccountNo("  AB1234".to_owned()));
+    assert_eq!(diagnostics.len(), 1);
+}
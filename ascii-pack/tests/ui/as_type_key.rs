@@ -0,0 +1,9 @@
+use ascii_pack::AsciiPack;
+
+#[derive(AsciiPack, Default)]
+pub struct Count {
+    #[pack(size = 6, as_type = u32)]
+    pub value: u64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ascii_pack::AsciiPack;
+
+#[derive(AsciiPack, Default)]
+pub struct Amount {
+    #[pack(size = 8, decimal = '.', grouping = '.')]
+    pub value: f64,
+}
+
+fn main() {}
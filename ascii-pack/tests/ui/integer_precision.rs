@@ -0,0 +1,9 @@
+use ascii_pack::AsciiPack;
+
+#[derive(AsciiPack, Default)]
+pub struct Count {
+    #[pack(size = 6, precision = 2)]
+    pub value: i64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ascii_pack::AsciiPack;
+
+#[derive(AsciiPack, Default)]
+pub struct Count {
+    #[pack(size = 4, align = "left", pad = '0', trim)]
+    pub value: u16,
+}
+
+fn main() {}
@@ -4,7 +4,6 @@
 
 use ascii_pack::until;
 use ascii_pack::AsciiPack;
-use ascii_pack::AsciiPackError;
 use ntest_timeout::timeout;
 
 // Note: the formatting here is intentional
@@ -65,11 +64,9 @@ fn test_unsized_nested_repeat() {
     let record = MultipleDays::from_ascii(EXAMPLE).unwrap();
 
     assert_eq!(record.days.len(), 3);
-    let mut num = 0;
-    for day in record.days.iter() {
+    for (num, day) in record.days.iter().enumerate() {
         assert_eq!(day.day_num, num);
         assert_eq!(day.vec.len(), 4);
-        num += 1;
     }
 
     assert_eq!(record.end_list.len(), 4);
@@ -0,0 +1,97 @@
+use ascii_pack::{until, AsciiPack, AsciiPackError, Static};
+
+/// `0042ALPHA `
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct OldDetail {
+    #[pack(size = 4)]
+    pub id: u32,
+
+    #[pack(size = 6)]
+    pub name: String,
+}
+
+/// `ALPHA 00420017`
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct NewDetail {
+    #[pack(size = 6)]
+    pub name: String,
+
+    #[pack(size = 4)]
+    pub id: u32,
+
+    #[pack(size = 4)]
+    pub branch: u32,
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug)]
+#[pack_enum(untagged)]
+pub enum Detail {
+    Old(#[pack] OldDetail),
+    New(#[pack] NewDetail),
+}
+
+impl Default for Detail {
+    fn default() -> Self {
+        Detail::Old(OldDetail::default())
+    }
+}
+
+#[derive(AsciiPack, PartialEq, Eq, Debug, Default)]
+pub struct Details {
+    #[pack_vec(until = until::starts_with(";"))]
+    pub details: Vec<Detail>,
+
+    #[pack_static(text = ";")]
+    pub end: Static,
+}
+
+#[test]
+fn untagged_enum() {
+    let old = Detail::from_ascii("0042ALPHA ").unwrap();
+    assert_eq!(
+        old,
+        Detail::Old(OldDetail {
+            id: 42,
+            name: "ALPHA ".to_owned()
+        })
+    );
+    assert_eq!(old.to_ascii().unwrap(), "0042ALPHA ");
+
+    let new = Detail::from_ascii("ALPHA 00420017").unwrap();
+    assert_eq!(
+        new,
+        Detail::New(NewDetail {
+            name: "ALPHA ".to_owned(),
+            id: 42,
+            branch: 17
+        })
+    );
+    assert_eq!(new.to_ascii().unwrap(), "ALPHA 00420017");
+}
+
+#[test]
+fn untagged_enum_requires_whole_input() {
+    // the old layout unpacks the first 10 characters, but leaves input behind
+    let detail = Detail::from_ascii("0042ALPHA 0017").unwrap_err();
+    match detail {
+        AsciiPackError::NoMatchingVariant { name, errors } => {
+            assert_eq!(name, "Detail");
+            assert_eq!(errors.len(), 2);
+            assert_eq!(errors[0].0, "Old");
+            assert_eq!(errors[1].0, "New");
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[test]
+fn untagged_enum_unsized() {
+    const EXAMPLE: &str = "0042ALPHA BETA  001100120001GAMMA ;";
+
+    let details = Details::from_ascii(EXAMPLE).unwrap();
+    assert_eq!(details.details.len(), 3);
+    assert!(matches!(details.details[0], Detail::Old(_)));
+    assert!(matches!(details.details[1], Detail::New(_)));
+    assert!(matches!(details.details[2], Detail::Old(_)));
+    assert_eq!(details.to_ascii().unwrap(), EXAMPLE);
+}